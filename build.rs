@@ -0,0 +1,102 @@
+//! Pre-collects the bundled PHP stubs into a serialised `DefinitionCollection`
+//! for each supported PHP version, so that they can be embedded in the binary
+//! without being parsed on every run.
+
+#![allow(dead_code)]
+
+use std::{
+    env,
+    fs::{read, read_dir, write},
+    path::{Path, PathBuf},
+};
+
+use definitions::{collection::DefinitionCollection, collector::DefinitionCollector};
+
+// The definition types and collector are shared with the main crate. They only
+// depend on `shared` and on `Context::resolve_name`, so a minimal `Context` is
+// provided below instead of pulling in the whole analyser.
+#[path = "src/definitions/mod.rs"]
+mod definitions;
+#[path = "src/shared/mod.rs"]
+mod shared;
+
+mod analyser {
+    pub mod context {
+        use pxp_parser::lexer::byte_string::ByteString;
+
+        #[derive(Debug, Default)]
+        pub struct Context;
+
+        impl Context {
+            pub fn resolve_name(&self, name: &ByteString) -> ByteString {
+                name.clone()
+            }
+        }
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=stubs");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let versions = stub_versions(Path::new("stubs"));
+
+    for (index, version) in versions.iter().enumerate() {
+        let mut collection = DefinitionCollection::new();
+
+        // Each version directory only contains the definitions that were added in
+        // that version, so a version's stubs are the sum of all versions up to it.
+        for previous in &versions[..=index] {
+            for file in stub_files(&Path::new("stubs").join(previous)) {
+                println!("cargo:rerun-if-changed={}", file.display());
+
+                let contents = read(&file).unwrap();
+                let mut ast = pxp_parser::parse(&contents).unwrap_or_else(|error| {
+                    panic!("failed to parse stub {}: {}", file.display(), error)
+                });
+
+                let mut collector = DefinitionCollector::new();
+                collector.scan(&mut ast);
+                collection.merge(collector.collect());
+            }
+        }
+
+        write(
+            out_dir.join(format!("stubs-{version}.json")),
+            serde_json::to_string(&collection).unwrap(),
+        )
+        .unwrap();
+    }
+}
+
+fn stub_versions(directory: &Path) -> Vec<String> {
+    let mut versions = read_dir(directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+        .collect::<Vec<String>>();
+
+    versions.sort_by_key(|version| {
+        version
+            .split('.')
+            .map(|part| part.parse::<u32>().unwrap())
+            .collect::<Vec<u32>>()
+    });
+
+    versions
+}
+
+fn stub_files(directory: &Path) -> Vec<PathBuf> {
+    let mut files = read_dir(directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension()
+                .map_or(false, |extension| extension == "php")
+        })
+        .collect::<Vec<PathBuf>>();
+
+    files.sort();
+    files
+}
@@ -0,0 +1,54 @@
+use std::{any::Any, collections::BTreeMap};
+
+use super::messages::MessageCollector;
+
+/// The data that rules collected while analysing each file of the project, which is
+/// handed to the project rules once the analysis has finished.
+#[derive(Debug, Default)]
+pub struct CollectedData<'a> {
+    message_collections: &'a [MessageCollector],
+}
+
+impl<'a> CollectedData<'a> {
+    pub fn new(message_collections: &'a [MessageCollector]) -> Self {
+        Self {
+            message_collections,
+        }
+    }
+
+    /// Every collected value of the given type, with the file that it was collected from.
+    pub fn get<T: Any>(&self) -> impl Iterator<Item = (&'a str, &'a T)> + 'a {
+        let message_collections = self.message_collections;
+
+        message_collections.iter().flat_map(|messages| {
+            messages
+                .collected()
+                .iter()
+                .filter_map(|data| data.downcast_ref::<T>())
+                .map(move |data| (messages.get_file(), data))
+        })
+    }
+}
+
+/// The messages reported by a project rule, which can be in any file.
+#[derive(Debug, Default)]
+pub struct ProjectMessages {
+    files: BTreeMap<String, MessageCollector>,
+}
+
+impl ProjectMessages {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The collector for the messages about the given file.
+    pub fn file(&mut self, file: &str) -> &mut MessageCollector {
+        self.files
+            .entry(file.to_string())
+            .or_insert_with(|| MessageCollector::new(file.to_string()))
+    }
+
+    pub fn into_collections(self) -> impl Iterator<Item = MessageCollector> {
+        self.files.into_values()
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{definitions::collection::DefinitionCollection, shared::types::Type};
 use pxp_parser::{
@@ -12,12 +12,15 @@ use pxp_parser::{
             ComparisonOperationExpression,
         },
         variables::{SimpleVariable, Variable},
-        CastExpression, CloneExpression, DefaultMatchArm, ErrorSuppressExpression, Expression,
-        FunctionCallExpression, MagicConstantExpression, MatchArmBody, MatchExpression,
-        NewExpression, ParenthesizedExpression, ReferenceExpression, ShortMatchExpression,
+        CloneExpression, DefaultMatchArm, ErrorSuppressExpression, Expression,
+        MagicConstantExpression, MatchArmBody, MatchExpression, NewExpression,
+        ParenthesizedExpression, ReferenceExpression, ReturnStatement, ShortMatchExpression,
+        Statement,
     },
 };
 
+use super::extensions::DynamicReturnTypeExtension;
+
 #[derive(Debug, Clone)]
 pub struct Context {
     namespace: ByteString,
@@ -25,6 +28,7 @@ pub struct Context {
     variables: HashMap<ByteString, Type>,
     classish_context: Option<ByteString>,
     function_context: Option<ByteString>,
+    extensions: Arc<[Arc<dyn DynamicReturnTypeExtension>]>,
 }
 
 impl Context {
@@ -35,6 +39,15 @@ impl Context {
             variables: HashMap::new(),
             classish_context: None,
             function_context: None,
+            extensions: Arc::new([]),
+        }
+    }
+
+    /// Create a context that asks the given extensions for the types of calls.
+    pub fn with_extensions(extensions: Arc<[Arc<dyn DynamicReturnTypeExtension>]>) -> Self {
+        Self {
+            extensions,
+            ..Self::new()
         }
     }
 
@@ -53,6 +66,7 @@ impl Context {
             variables: HashMap::new(),
             classish_context: self.classish_context.clone(),
             function_context: self.function_context.clone(),
+            extensions: self.extensions.clone(),
         }
     }
 
@@ -90,15 +104,24 @@ impl Context {
             Expression::Variable(Variable::SimpleVariable(SimpleVariable { name, .. })) => {
                 self.variables.get(name).cloned().unwrap_or(Type::Mixed)
             }
-            Expression::FunctionCall(FunctionCallExpression { target, .. }) => {
-                match target.as_ref() {
+            Expression::FunctionCall(call) => {
+                match call.target.as_ref() {
                     Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
                         value: function_name,
                         ..
                     })) => {
-                        if let Some(function_definition) =
-                            definitions.get_function(function_name, self)
-                        {
+                        let function_definition = definitions.get_function(function_name, self);
+                        let name = function_definition
+                            .map(|function| function.name.clone())
+                            .unwrap_or_else(|| self.resolve_name(function_name));
+
+                        if let Some(ty) = self.extensions.iter().find_map(|extension| {
+                            extension.get_function_call_type(&name, call, definitions, self)
+                        }) {
+                            return ty;
+                        }
+
+                        if let Some(function_definition) = function_definition {
                             if let Some(return_type) = function_definition.return_type.as_ref() {
                                 return_type.clone()
                             } else {
@@ -248,74 +271,12 @@ impl Context {
                 AssignmentOperationExpression::Assign { right, .. } => {
                     self.get_type(right.as_ref(), definitions)
                 }
-                AssignmentOperationExpression::Addition {
-                    left,
-                    plus_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::Subtraction {
-                    left,
-                    minus_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::Multiplication {
-                    left,
-                    asterisk_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::Division {
-                    left,
-                    slash_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::Modulo {
-                    left,
-                    percent_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::Exponentiation {
-                    left,
-                    pow_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::Concat {
-                    left,
-                    dot_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::BitwiseAnd {
-                    left,
-                    ampersand_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::BitwiseOr {
-                    left,
-                    pipe_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::BitwiseXor {
-                    left,
-                    caret_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::LeftShift {
-                    left,
-                    left_shift_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::RightShift {
-                    left,
-                    right_shift_equals,
-                    right,
-                } => todo!(),
-                AssignmentOperationExpression::Coalesce {
-                    left,
-                    coalesce_equals,
-                    right,
-                } => todo!(),
+                // TODO: Work out the types of compound assignments, e.g. `$a += 1`.
+                _ => Type::Mixed,
             },
-            Expression::BitwiseOperation(_) => todo!(),
-            Expression::RangeOperation(_) => todo!(),
+            // PXP's ranges, e.g. `1..10` or `1..`, are lazily iterated over rather than
+            // built up front, and an endless range can't be an array.
+            Expression::RangeOperation(_) => Type::Iterable,
             Expression::Concat(_) => Type::String,
             Expression::Instanceof(_) => Type::Bool,
             Expression::Reference(ReferenceExpression { right, .. }) => {
@@ -323,16 +284,82 @@ impl Context {
             }
             Expression::Identifier(_) => unreachable!(),
             Expression::FunctionClosureCreation(_) => Type::Callable,
-            Expression::MethodCall(_) => todo!(),
-            Expression::MethodClosureCreation(_) => todo!(),
-            Expression::NullsafeMethodCall(_) => todo!(),
-            Expression::StaticMethodCall(_) => todo!(),
-            Expression::StaticVariableMethodCall(_) => todo!(),
-            Expression::StaticMethodClosureCreation(_) => todo!(),
-            Expression::StaticVariableMethodClosureCreation(_) => todo!(),
-            Expression::PropertyFetch(_) => todo!(),
-            Expression::NullsafePropertyFetch(_) => todo!(),
-            Expression::StaticPropertyFetch(_) => todo!(),
+            Expression::MethodCall(call) => {
+                let method = match call.method.as_ref() {
+                    Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                        value,
+                        ..
+                    })) => value,
+                    _ => return Type::Mixed,
+                };
+
+                let class = match call.target.as_ref() {
+                    Expression::Variable(Variable::SimpleVariable(SimpleVariable {
+                        name, ..
+                    })) if name == &ByteString::from(b"$this") && self.is_in_class() => {
+                        self.resolve_name(self.classish_context())
+                    }
+                    // Types from parameters are written as they are in the code, so they
+                    // have to be resolved to the fully qualified name of the class.
+                    target => match self.get_type(target, definitions) {
+                        Type::Named(class) => definitions
+                            .get_class(&class, self)
+                            .map(|class| class.name.clone())
+                            .or_else(|| {
+                                definitions
+                                    .get_interface(&class, self)
+                                    .map(|interface| interface.name.clone())
+                            })
+                            .unwrap_or_else(|| self.resolve_name(&class)),
+                        _ => return Type::Mixed,
+                    },
+                };
+
+                if let Some(ty) = self.extensions.iter().find_map(|extension| {
+                    extension.get_method_call_type(&class, method, call, definitions, self)
+                }) {
+                    return ty;
+                }
+
+                self.get_method_return_type(&class, method, definitions)
+            }
+            Expression::StaticMethodCall(call) => {
+                let method = match &call.method {
+                    Identifier::SimpleIdentifier(SimpleIdentifier { value, .. }) => value,
+                    _ => return Type::Mixed,
+                };
+
+                let class = match call.target.as_ref() {
+                    Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                        value,
+                        ..
+                    })) => definitions
+                        .get_class(value, self)
+                        .map(|class| class.name.clone())
+                        .unwrap_or_else(|| self.resolve_name(value)),
+                    Expression::Self_ | Expression::Static if self.is_in_class() => {
+                        self.resolve_name(self.classish_context())
+                    }
+                    Expression::Parent if self.is_in_class() => {
+                        match definitions
+                            .get_class(self.classish_context(), self)
+                            .and_then(|class| class.extends.clone())
+                        {
+                            Some(parent) => parent,
+                            None => return Type::Mixed,
+                        }
+                    }
+                    _ => return Type::Mixed,
+                };
+
+                if let Some(ty) = self.extensions.iter().find_map(|extension| {
+                    extension.get_static_method_call_type(&class, method, call, definitions, self)
+                }) {
+                    return ty;
+                }
+
+                self.get_method_return_type(&class, method, definitions)
+            }
             Expression::ConstantFetch(_) => Type::Mixed,
             Expression::Static => unreachable!(),
             Expression::Self_ => unreachable!(),
@@ -352,7 +379,7 @@ impl Context {
             Expression::Heredoc(_) => Type::String,
             Expression::Nowdoc(_) => Type::String,
             Expression::ShellExec(_) => Type::String,
-            Expression::AnonymousClass(_) => unreachable!(),
+            Expression::AnonymousClass(_) => Type::Object,
             // TODO: Make this more accurate once we have a better knowledge of
             //       the values stored inside of an array.
             Expression::ArrayIndex(_) => Type::Mixed,
@@ -367,9 +394,6 @@ impl Context {
                 MagicConstantExpression::Trait(_) => Type::String,
                 MagicConstantExpression::CompilerHaltOffset(_) => Type::Int,
             },
-            Expression::ShortTernary(_) => todo!(),
-            Expression::Ternary(_) => todo!(),
-            Expression::Coalesce(_) => todo!(),
             Expression::Clone(CloneExpression { target }) => {
                 self.get_type(target.as_ref(), definitions)
             }
@@ -377,23 +401,11 @@ impl Context {
                 let mut types = vec![];
 
                 for arm in arms.iter() {
-                    types.push(self.get_type(
-                        match &arm.body {
-                            MatchArmBody::Block { statements, .. } => todo!(),
-                            MatchArmBody::Expression { expression } => &expression,
-                        },
-                        definitions,
-                    ));
+                    types.push(self.get_match_arm_type(&arm.body, definitions));
                 }
 
                 if let Some(default) = default {
-                    types.push(self.get_type(
-                        match &default.as_ref().body {
-                            MatchArmBody::Block { statements, .. } => todo!(),
-                            MatchArmBody::Expression { expression } => &expression,
-                        },
-                        definitions,
-                    ));
+                    types.push(self.get_match_arm_type(&default.as_ref().body, definitions));
                 }
 
                 Type::Union(types)
@@ -402,37 +414,73 @@ impl Context {
                 let mut types = vec![];
 
                 for arm in arms.iter() {
-                    types.push(self.get_type(
-                        match &arm.body {
-                            MatchArmBody::Block { statements, .. } => todo!(),
-                            MatchArmBody::Expression { expression } => &expression,
-                        },
-                        definitions,
-                    ));
+                    types.push(self.get_match_arm_type(&arm.body, definitions));
                 }
 
                 if let Some(default) = default {
-                    types.push(self.get_type(
-                        match &default.as_ref().body {
-                            MatchArmBody::Block { statements, .. } => todo!(),
-                            MatchArmBody::Expression { expression } => &expression,
-                        },
-                        definitions,
-                    ));
+                    types.push(self.get_match_arm_type(&default.as_ref().body, definitions));
                 }
 
                 Type::Union(types)
             }
             Expression::Throw(_) => Type::Never,
-            Expression::Yield(_) => todo!(),
-            Expression::YieldFrom(_) => todo!(),
-            Expression::Cast(CastExpression { kind, value, .. }) => {
-                match (kind, self.get_type(value, definitions)) {
-                    _ => todo!(),
+            // TODO: Work out the types of property fetches, ternaries, casts and the
+            //       remaining expressions. Until then, rules can ask for the type of
+            //       any expression without having to check what it is first.
+            _ => Type::Mixed,
+        }
+    }
+
+    /// The declared return type of a method, including inherited methods and those
+    /// from traits. Methods that return `static` or `self` return the class itself.
+    fn get_method_return_type(
+        &self,
+        class: &ByteString,
+        method: &ByteString,
+        definitions: &DefinitionCollection,
+    ) -> Type {
+        let class = match definitions.get_class(class, self) {
+            Some(class) => class,
+            None => return Type::Mixed,
+        };
+
+        let method = class.get_method(method, definitions, self).or_else(|| {
+            class
+                .get_inherited_method(method, definitions, self)
+                .map(|(_, method)| method)
+        });
+
+        match method.and_then(|method| method.return_type.clone()) {
+            Some(Type::Static | Type::Self_) => Type::Named(class.name.clone()),
+            Some(return_type) => return_type,
+            None => Type::Mixed,
+        }
+    }
+
+    fn get_match_arm_type(&self, body: &MatchArmBody, definitions: &DefinitionCollection) -> Type {
+        match body {
+            MatchArmBody::Expression { expression } => self.get_type(expression, definitions),
+            // PXP allows block bodies in match arms, which produce the arm's value with
+            // a `return` statement. If there isn't one, we don't know what they produce.
+            MatchArmBody::Block { statements, .. } => {
+                let types = statements
+                    .iter()
+                    .filter_map(|statement| match statement {
+                        Statement::Return(ReturnStatement { value, .. }) => Some(
+                            value
+                                .as_ref()
+                                .map_or(Type::Null, |value| self.get_type(value, definitions)),
+                        ),
+                        _ => None,
+                    })
+                    .collect::<Vec<Type>>();
+
+                if types.is_empty() {
+                    Type::Mixed
+                } else {
+                    Type::Union(types)
                 }
             }
-            Expression::Noop => todo!(),
-            _ => Type::Mixed,
         }
     }
 
@@ -488,3 +536,64 @@ impl Default for Context {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use pxp_parser::{lexer::byte_string::ByteString, parser::ast::MethodCallExpression};
+
+    use crate::{
+        analyser::{extensions::DynamicReturnTypeExtension, Analyser},
+        definitions::collection::DefinitionCollection,
+        rules::dump_type::DumpTypeRule,
+        shared::types::Type,
+    };
+
+    use super::Context;
+
+    /// Types every call as the class that the method was called on.
+    #[derive(Debug)]
+    struct CalledClassExtension;
+
+    impl DynamicReturnTypeExtension for CalledClassExtension {
+        fn get_method_call_type(
+            &self,
+            class: &ByteString,
+            _method: &ByteString,
+            _call: &MethodCallExpression,
+            _definitions: &DefinitionCollection,
+            _context: &Context,
+        ) -> Option<Type> {
+            Some(Type::Named(class.clone()))
+        }
+    }
+
+    #[test]
+    fn it_passes_fully_qualified_class_names_to_extensions() {
+        let mut analyser = Analyser::new(Arc::new(DefinitionCollection::default()));
+        analyser.add_rule(Box::new(DumpTypeRule));
+        analyser.add_extension(Arc::new(CalledClassExtension));
+
+        let messages = analyser.analyse(
+            "test.php".to_string(),
+            br"<?php
+            namespace App;
+
+            use Psr\Container\ContainerInterface;
+
+            function make(ContainerInterface $container) {
+                dumpType($container->get(Foo::class));
+            }",
+        );
+        let messages = messages
+            .iter()
+            .map(|message| message.message.as_str())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(
+            messages,
+            vec![r"Dumped type: \Psr\Container\ContainerInterface"]
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::Debug;
+
+use pxp_parser::{
+    lexer::byte_string::ByteString,
+    parser::ast::{FunctionCallExpression, MethodCallExpression, StaticMethodCallExpression},
+};
+
+use crate::{definitions::collection::DefinitionCollection, shared::types::Type};
+
+use super::context::Context;
+
+/// Computes the return type of calls that can't be typed from their declarations
+/// alone, such as `app('foo')`, `Model::query()` or `$container->get(Foo::class)`.
+///
+/// Extensions are registered with `Analyser::add_extension` and are asked for the
+/// type of a call before its declared return type is used. Each method returns
+/// `None` to leave the call to the next extension, or to the declared return type
+/// if no extension knows the type.
+pub trait DynamicReturnTypeExtension: Debug + Send + Sync {
+    /// The type of a call to the function with the given fully qualified name.
+    fn get_function_call_type(
+        &self,
+        _function: &ByteString,
+        _call: &FunctionCallExpression,
+        _definitions: &DefinitionCollection,
+        _context: &Context,
+    ) -> Option<Type> {
+        None
+    }
+
+    /// The type of a call to the given method on an instance of the class with the
+    /// given fully qualified name.
+    fn get_method_call_type(
+        &self,
+        _class: &ByteString,
+        _method: &ByteString,
+        _call: &MethodCallExpression,
+        _definitions: &DefinitionCollection,
+        _context: &Context,
+    ) -> Option<Type> {
+        None
+    }
+
+    /// The type of a static call to the given method on the class with the given
+    /// fully qualified name.
+    fn get_static_method_call_type(
+        &self,
+        _class: &ByteString,
+        _method: &ByteString,
+        _call: &StaticMethodCallExpression,
+        _definitions: &DefinitionCollection,
+        _context: &Context,
+    ) -> Option<Type> {
+        None
+    }
+}
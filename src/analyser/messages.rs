@@ -1,19 +1,144 @@
-use std::{fmt::Display, slice::Iter};
+use std::{any::Any, fmt::Display, slice::Iter, str::FromStr, sync::Arc};
 
-#[derive(Debug, Clone)]
+use pxp_parser::lexer::token::Span;
+use serde::{Deserialize, Serialize};
+
+use crate::definitions::location::DefinitionLocation;
+
+use super::suppressions::Suppressions;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub severity: MessageSeverity,
+    /// A stable identifier for the kind of message, e.g. `function.notFound`.
+    pub identifier: String,
     pub message: String,
     pub line: usize,
+    pub column: usize,
+    /// The byte offset of the start of the offending code.
+    pub start: usize,
+    /// The byte offset of the end of the offending code. This is the same as `start`
+    /// when only the start of the code is known.
+    pub end: usize,
+}
+
+/// The position of the code that a message is reported for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessageSpan {
+    pub line: usize,
+    pub column: usize,
+    pub start: usize,
+    pub end: usize,
 }
 
-#[derive(Debug, Clone)]
+impl MessageSpan {
+    /// A span covering everything from the start of one token to the end of another,
+    /// single character token, such as the `)` that closes a call.
+    pub fn between(start: Span, end: Span) -> Self {
+        Self {
+            line: start.line,
+            column: start.column,
+            start: start.position,
+            end: (end.position + 1).max(start.position),
+        }
+    }
+
+    /// A span covering the given number of bytes from the start of a token, e.g. the
+    /// whole of a name.
+    pub fn with_length(start: Span, length: usize) -> Self {
+        Self {
+            line: start.line,
+            column: start.column,
+            start: start.position,
+            end: start.position + length,
+        }
+    }
+
+    /// Find the span of the given line and column in the contents of a file.
+    pub fn at(contents: &[u8], line: usize, column: usize) -> Self {
+        let line_start = contents
+            .split(|byte| *byte == b'\n')
+            .take(line.saturating_sub(1))
+            .map(|line| line.len() + 1)
+            .sum::<usize>();
+
+        let start = (line_start + column.saturating_sub(1)).min(contents.len());
+
+        Self {
+            line,
+            column,
+            start,
+            end: start,
+        }
+    }
+}
+
+impl From<Span> for MessageSpan {
+    fn from(span: Span) -> Self {
+        Self {
+            line: span.line,
+            column: span.column,
+            start: span.position,
+            end: span.position,
+        }
+    }
+}
+
+impl From<&Span> for MessageSpan {
+    fn from(span: &Span) -> Self {
+        (*span).into()
+    }
+}
+
+impl From<&DefinitionLocation> for MessageSpan {
+    fn from(location: &DefinitionLocation) -> Self {
+        Self {
+            line: location.line,
+            column: location.column,
+            start: location.offset,
+            end: location.offset,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum MessageSeverity {
     Error,
     Warning,
     Note,
 }
 
+impl MessageSeverity {
+    /// Check if this severity is at least as severe as the given one.
+    pub fn is_at_least(&self, severity: MessageSeverity) -> bool {
+        self.rank() >= severity.rank()
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            MessageSeverity::Error => 2,
+            MessageSeverity::Warning => 1,
+            MessageSeverity::Note => 0,
+        }
+    }
+}
+
+impl FromStr for MessageSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(MessageSeverity::Error),
+            "warning" => Ok(MessageSeverity::Warning),
+            "note" => Ok(MessageSeverity::Note),
+            _ => Err(format!(
+                "unknown severity {s}, expected one of error, warning or note"
+            )),
+        }
+    }
+}
+
 impl Display for MessageSeverity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -24,10 +149,17 @@ impl Display for MessageSeverity {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct MessageCollector {
     file: String,
     messages: Vec<Message>,
+    /// Data collected by rules for the project rules, see `CollectedData`.
+    #[serde(skip)]
+    collected: Vec<Arc<dyn Any + Send + Sync>>,
+    /// The `@statan-ignore` comments in the file, which are applied to the messages
+    /// that are merged in later on.
+    #[serde(skip)]
+    suppressions: Suppressions,
 }
 
 impl MessageCollector {
@@ -35,9 +167,63 @@ impl MessageCollector {
         Self {
             file,
             messages: Vec::new(),
+            collected: Vec::new(),
+            suppressions: Suppressions::default(),
         }
     }
 
+    /// Keep some data about the file for the project rules, which run once every
+    /// file has been analysed. Project rules look the data up by its type, so rules
+    /// should collect types of their own.
+    pub fn collect(&mut self, data: impl Any + Send + Sync) {
+        self.collected.push(Arc::new(data));
+    }
+
+    pub fn collected(&self) -> &[Arc<dyn Any + Send + Sync>] {
+        &self.collected
+    }
+
+    pub fn set_suppressions(&mut self, suppressions: Suppressions) {
+        self.suppressions = suppressions;
+    }
+
+    /// Add the messages and collected data from another collector to this one. The
+    /// file's suppressions are applied to the added messages, since they're reported
+    /// after the file was analysed, e.g. by the project rules.
+    pub fn merge(&mut self, mut other: MessageCollector) {
+        if !self.suppressions.is_empty() {
+            let unmatched = self
+                .suppressions
+                .unmatched()
+                .map(|suppression| suppression.span)
+                .collect::<Vec<MessageSpan>>();
+
+            self.suppressions.apply(&mut other);
+
+            // Suppressions that only match the added messages were reported as unmatched
+            // when the file was analysed.
+            let matched = unmatched
+                .into_iter()
+                .filter(|span| {
+                    !self
+                        .suppressions
+                        .unmatched()
+                        .any(|suppression| suppression.span == *span)
+                })
+                .collect::<Vec<MessageSpan>>();
+
+            self.messages.retain(|message| {
+                message.identifier != "ignore.unmatched"
+                    || !matched
+                        .iter()
+                        .any(|span| span.line == message.line && span.start == message.start)
+            });
+        }
+
+        self.messages.extend(other.messages);
+        self.collected.extend(other.collected);
+    }
+
     pub fn iter(&self) -> Iter<Message> {
         self.messages.iter()
     }
@@ -46,27 +232,82 @@ impl MessageCollector {
         self.file.as_str()
     }
 
-    pub fn error(&mut self, message: impl Into<String>, line: usize) {
-        self.messages.push(Message {
-            severity: MessageSeverity::Error,
-            message: message.into(),
-            line,
-        });
+    pub fn len(&self) -> usize {
+        self.messages.len()
     }
 
-    pub fn warning(&mut self, message: impl Into<String>, line: usize) {
-        self.messages.push(Message {
-            severity: MessageSeverity::Warning,
-            message: message.into(),
-            line,
-        });
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
     }
 
-    pub fn note(&mut self, message: impl Into<String>, line: usize) {
+    pub fn retain(&mut self, predicate: impl FnMut(&Message) -> bool) {
+        self.messages.retain(predicate);
+    }
+
+    /// Change the severity of every message that was added after the given index.
+    pub fn set_severity_since(&mut self, index: usize, severity: MessageSeverity) {
+        for message in self.messages.iter_mut().skip(index) {
+            message.severity = severity;
+        }
+    }
+
+    fn add(
+        &mut self,
+        severity: MessageSeverity,
+        identifier: String,
+        message: String,
+        span: MessageSpan,
+    ) {
         self.messages.push(Message {
-            severity: MessageSeverity::Note,
-            message: message.into(),
-            line,
+            severity,
+            identifier,
+            message,
+            line: span.line,
+            column: span.column,
+            start: span.start,
+            end: span.end,
         });
     }
+
+    pub fn error(
+        &mut self,
+        identifier: impl Into<String>,
+        message: impl Into<String>,
+        span: impl Into<MessageSpan>,
+    ) {
+        self.add(
+            MessageSeverity::Error,
+            identifier.into(),
+            message.into(),
+            span.into(),
+        );
+    }
+
+    pub fn warning(
+        &mut self,
+        identifier: impl Into<String>,
+        message: impl Into<String>,
+        span: impl Into<MessageSpan>,
+    ) {
+        self.add(
+            MessageSeverity::Warning,
+            identifier.into(),
+            message.into(),
+            span.into(),
+        );
+    }
+
+    pub fn note(
+        &mut self,
+        identifier: impl Into<String>,
+        message: impl Into<String>,
+        span: impl Into<MessageSpan>,
+    ) {
+        self.add(
+            MessageSeverity::Note,
+            identifier.into(),
+            message.into(),
+            span.into(),
+        );
+    }
 }
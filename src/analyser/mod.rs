@@ -1,3 +1,5 @@
+use std::{collections::HashMap, sync::Arc};
+
 use pxp_parser::{
     downcast::downcast,
     lexer::byte_string::ByteString,
@@ -8,61 +10,263 @@ use pxp_parser::{
         functions::FunctionStatement,
         identifiers::SimpleIdentifier,
         namespaces::{BracedNamespace, UnbracedNamespace},
-        GroupUseStatement, Use, UseStatement,
+        operators::AssignmentOperationExpression,
+        variables::{SimpleVariable, Variable},
+        Expression, GroupUseStatement, Statement, Use, UseStatement,
     },
     traverser::Visitor,
 };
 
 use crate::{definitions::collection::DefinitionCollection, rules::Rule, shared::types::Type};
 
-use self::{context::Context, messages::MessageCollector};
+use self::{
+    context::Context,
+    extensions::DynamicReturnTypeExtension,
+    messages::{MessageCollector, MessageSeverity, MessageSpan},
+    panics::Panic,
+    suppressions::Suppressions,
+};
 
+pub mod collected;
 pub mod context;
+pub mod extensions;
 pub mod messages;
+pub mod panics;
+pub mod suppressions;
 
 #[derive(Debug)]
 pub struct Analyser {
     rules: Vec<Box<dyn Rule>>,
-    definitions: DefinitionCollection,
+    extensions: Vec<Arc<dyn DynamicReturnTypeExtension>>,
+    severities: HashMap<String, MessageSeverity>,
+    definitions: Arc<DefinitionCollection>,
     message_collector: MessageCollector,
     context_stack: Vec<Context>,
+    report_unmatched_suppressions: bool,
+    verbose: bool,
+    current_rule: Option<&'static str>,
 }
 
 impl Analyser {
-    pub fn new(definitions: DefinitionCollection) -> Self {
+    pub fn new(definitions: Arc<DefinitionCollection>) -> Self {
         Self {
             rules: Vec::new(),
+            extensions: Vec::new(),
+            severities: HashMap::new(),
             definitions,
             message_collector: MessageCollector::default(),
             context_stack: Vec::new(),
+            report_unmatched_suppressions: true,
+            verbose: false,
+            current_rule: None,
         }
     }
 
     pub fn analyse(&mut self, file: String, contents: &[u8]) -> MessageCollector {
-        self.message_collector = MessageCollector::new(file);
+        let parse_result = match panics::catch(|| parse(contents)) {
+            Ok(parse_result) => parse_result,
+            Err(panic) => {
+                self.message_collector = MessageCollector::new(file);
+                self.report_panic(panic, contents);
+                return self.message_collector.clone();
+            }
+        };
 
-        let parse_result = parse(contents);
         if let Err(error) = parse_result {
-            self.message_collector.error(error.to_string(), 0);
+            let message = error.to_string();
+            let span = parse_error_span(contents, &message);
+
+            self.message_collector = MessageCollector::new(file);
+            self.message_collector.error("parse.error", message, span);
             return self.message_collector.clone();
         }
 
         let mut ast = parse_result.unwrap();
 
-        self.context_stack.push(Context::new());
-        self.visit_node(&mut ast).unwrap();
+        self.analyse_ast(file, contents, &mut ast)
+    }
+
+    pub fn analyse_ast(
+        &mut self,
+        file: String,
+        contents: &[u8],
+        ast: &mut [Statement],
+    ) -> MessageCollector {
+        self.message_collector = MessageCollector::new(file);
+        self.context_stack.clear();
+        self.current_rule = None;
+
+        self.context_stack
+            .push(Context::with_extensions(self.extensions.as_slice().into()));
+
+        // A bug in a single rule shouldn't stop the rest of the project from being
+        // analysed, so a panic is reported as an error in the file instead.
+        let result = panics::catch(|| {
+            self.run_rules(Hook::BeforeFile, None);
+
+            for statement in ast.iter_mut() {
+                self.visit_node(statement).unwrap();
+            }
+
+            self.run_rules(Hook::AfterFile, None);
+        });
+
+        if let Err(panic) = result {
+            self.report_panic(panic, contents);
+        }
+
+        let mut suppressions = Suppressions::parse(contents);
+        if !suppressions.is_empty() {
+            suppressions.apply(&mut self.message_collector);
+
+            if self.report_unmatched_suppressions {
+                suppressions.report_unmatched(&mut self.message_collector);
+            }
+        }
+
+        self.message_collector.set_suppressions(suppressions);
+
         self.message_collector.clone()
     }
 
     pub fn add_rule(&mut self, rule: Box<dyn Rule>) {
         self.rules.push(rule);
     }
+
+    /// Register an extension that computes the return types of calls. Extensions are
+    /// asked in the order they were added.
+    pub fn add_extension(&mut self, extension: Arc<dyn DynamicReturnTypeExtension>) {
+        self.extensions.push(extension);
+    }
+
+    /// Report `@statan-ignore` comments that don't match any message. Enabled by default.
+    pub fn set_report_unmatched_suppressions(&mut self, report: bool) {
+        self.report_unmatched_suppressions = report;
+    }
+
+    /// Include a backtrace in the messages reported for internal errors.
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
+    fn report_panic(&mut self, panic: Panic, contents: &[u8]) {
+        let mut message = match self.current_rule.take() {
+            Some(rule) => format!(
+                "Internal error while analysing {} in {}: {}",
+                self.message_collector.get_file(),
+                rule,
+                panic.message
+            ),
+            None => format!(
+                "Internal error while analysing {}: {}",
+                self.message_collector.get_file(),
+                panic.message
+            ),
+        };
+
+        match panic.backtrace {
+            Some(backtrace) if self.verbose => message.push_str(&format!("\n{backtrace}")),
+            _ => message.push_str("\nRun with --verbose to see a backtrace."),
+        }
+
+        self.message_collector
+            .error("internal.error", message, MessageSpan::at(contents, 1, 1));
+    }
+
+    /// Run the given hook of every rule. Node hooks are only run for the rules that
+    /// should run on the node.
+    fn run_rules(&mut self, hook: Hook, mut node: Option<&mut dyn Node>) {
+        let context = self.context_stack.last_mut().unwrap();
+
+        for rule in self.rules.iter_mut() {
+            if let Some(node) = node.as_deref() {
+                if !rule.should_run(node) {
+                    continue;
+                }
+            }
+
+            let reported = self.message_collector.len();
+            self.current_rule = Some(rule.name());
+
+            let definitions = &self.definitions;
+            let messages = &mut self.message_collector;
+
+            match (&hook, node.as_deref_mut()) {
+                (Hook::BeforeFile, _) => rule.before_file(definitions, messages),
+                (Hook::EnterNode, Some(node)) => {
+                    rule.enter_node(node, definitions, messages, context)
+                }
+                (Hook::LeaveNode, Some(node)) => {
+                    rule.leave_node(node, definitions, messages, context)
+                }
+                (Hook::AfterFile, _) => rule.after_file(definitions, messages),
+                _ => {}
+            }
+
+            self.current_rule = None;
+
+            if let Some(severity) = self.severities.get(rule.name()) {
+                self.message_collector
+                    .set_severity_since(reported, *severity);
+            }
+        }
+    }
+
+    /// Override the severity of every message reported by the rule with the given name.
+    pub fn set_severity(&mut self, rule: impl Into<String>, severity: MessageSeverity) {
+        self.severities.insert(rule.into(), severity);
+    }
+}
+
+/// The points during the analysis of a file at which rules are run.
+enum Hook {
+    BeforeFile,
+    EnterNode,
+    LeaveNode,
+    AfterFile,
+}
+
+/// The parser only describes the position of an error in its message, e.g.
+/// "unexpected token `}` on line 5 column 3", so the position is recovered from there.
+/// Errors without a position are reported at the start of the file.
+fn parse_error_span(contents: &[u8], message: &str) -> MessageSpan {
+    let position = message.rfind("on line ").and_then(|index| {
+        let mut parts = message[index + "on line ".len()..].split_whitespace();
+        let line = parts.next()?.parse::<usize>().ok()?;
+        let column = match (parts.next(), parts.next()) {
+            (Some("column"), Some(column)) => column
+                .trim_end_matches(|c: char| !c.is_ascii_digit())
+                .parse::<usize>()
+                .ok()?,
+            _ => 1,
+        };
+
+        Some((line, column))
+    });
+
+    let (line, column) = position.unwrap_or((1, 1));
+
+    MessageSpan::at(contents, line, column)
 }
 
 impl Visitor<()> for Analyser {
     fn visit_node(&mut self, node: &mut dyn Node) -> Result<(), ()> {
         self.visit(node)?;
 
+        // Variables are typed here rather than in a rule, so that their types are known
+        // whichever rules are enabled. Rules see the type from before the assignment.
+        if let Some(assignment @ AssignmentOperationExpression::Assign { .. }) =
+            downcast::<AssignmentOperationExpression>(node)
+        {
+            if let Expression::Variable(Variable::SimpleVariable(SimpleVariable { name, .. })) =
+                assignment.left()
+            {
+                let context = self.context_stack.last_mut().unwrap();
+                let value_type = context.get_type(assignment.right(), &self.definitions);
+                context.set_variable(name.clone(), value_type);
+            }
+        }
+
         let mut context = self.context_stack.last_mut().unwrap().clean();
         let mut did_push_context = false;
 
@@ -98,6 +302,8 @@ impl Visitor<()> for Analyser {
             self.context_stack.pop();
         }
 
+        self.run_rules(Hook::LeaveNode, Some(node));
+
         Ok(())
     }
 
@@ -142,17 +348,65 @@ impl Visitor<()> for Analyser {
             }
         }
 
-        for rule in &mut self.rules {
-            if rule.should_run(node) {
-                rule.run(
-                    node,
-                    &self.definitions,
-                    &mut self.message_collector,
-                    context,
-                );
-            }
-        }
+        self.run_rules(Hook::EnterNode, Some(node));
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        analyser::messages::MessageSpan, definitions::collection::DefinitionCollection,
+        rules::dump_type::DumpTypeRule,
+    };
+
+    use super::{parse_error_span, Analyser};
+
+    #[test]
+    fn it_finds_the_position_of_parse_errors() {
+        let contents = b"<?php\n\nfunction foo( {\n}\n";
+
+        assert_eq!(
+            parse_error_span(contents, "unexpected token `{` on line 3 column 15"),
+            MessageSpan {
+                line: 3,
+                column: 15,
+                start: 21,
+                end: 21,
+            }
+        );
+        assert_eq!(
+            parse_error_span(contents, "unexpected end of file on line 4").start,
+            23
+        );
+        assert_eq!(
+            parse_error_span(contents, "something went wrong"),
+            MessageSpan {
+                line: 1,
+                column: 1,
+                start: 0,
+                end: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn it_types_assigned_variables_without_the_assignment_rule() {
+        let mut analyser = Analyser::new(Arc::new(DefinitionCollection::default()));
+        analyser.add_rule(Box::new(DumpTypeRule));
+
+        let messages = analyser.analyse(
+            "test.php".to_string(),
+            b"<?php $count = 1; dumpType($count);",
+        );
+        let messages = messages
+            .iter()
+            .map(|message| message.message.as_str())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(messages, vec!["Dumped type: int"]);
+    }
+}
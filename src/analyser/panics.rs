@@ -0,0 +1,58 @@
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    cell::{Cell, RefCell},
+    panic::{catch_unwind, set_hook, take_hook, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// A panic that was caught while analysing a file.
+#[derive(Debug)]
+pub struct Panic {
+    pub message: String,
+    pub backtrace: Option<Backtrace>,
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = Cell::new(false);
+    static BACKTRACE: RefCell<Option<Backtrace>> = RefCell::new(None);
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Run the given function, turning a panic into an error instead of unwinding
+/// any further. The default panic message is not printed for caught panics, but
+/// the backtrace is kept so that it can be reported.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, Panic> {
+    INSTALL_HOOK.call_once(|| {
+        let previous = take_hook();
+
+        set_hook(Box::new(move |info| {
+            if CATCHING.with(|catching| catching.get()) {
+                BACKTRACE
+                    .with(|backtrace| *backtrace.borrow_mut() = Some(Backtrace::force_capture()));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+
+    let was_catching = CATCHING.with(|catching| catching.replace(true));
+    let result = catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|catching| catching.set(was_catching));
+
+    result.map_err(|payload| Panic {
+        message: panic_message(payload),
+        backtrace: BACKTRACE.with(|backtrace| backtrace.borrow_mut().take()),
+    })
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
@@ -0,0 +1,325 @@
+use super::messages::{MessageCollector, MessageSpan};
+
+const IGNORE_NEXT_LINE: &str = "@statan-ignore-next-line";
+const IGNORE_LINE: &str = "@statan-ignore-line";
+const IGNORE: &str = "@statan-ignore";
+
+/// A `@statan-ignore` comment found in a file.
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    /// The position of the comment.
+    pub span: MessageSpan,
+    /// The line whose messages are suppressed.
+    pub target: usize,
+    /// The identifiers of the messages that are suppressed. If empty, every message
+    /// on the target line is suppressed.
+    pub identifiers: Vec<String>,
+    used: bool,
+}
+
+impl Suppression {
+    fn matches(&self, line: usize, identifier: &str) -> bool {
+        self.target == line
+            && (self.identifiers.is_empty() || self.identifiers.iter().any(|i| i == identifier))
+    }
+}
+
+/// The suppression comments in a single file.
+///
+/// - `// @statan-ignore-next-line` suppresses every message on the following line.
+/// - `// @statan-ignore-line` suppresses every message on the same line.
+/// - `// @statan-ignore function.notFound, argument.type` only suppresses messages with
+///   the given identifiers. It applies to the same line when it follows some code,
+///   otherwise to the following line.
+///
+/// The `-next-line` and `-line` variants also accept a list of identifiers.
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    suppressions: Vec<Suppression>,
+}
+
+impl Suppressions {
+    pub fn parse(contents: &[u8]) -> Self {
+        let contents = String::from_utf8_lossy(contents);
+        let mut suppressions = Vec::new();
+        let mut offset = 0;
+
+        for (index, text) in contents.split('\n').enumerate() {
+            let line = index + 1;
+            let line_start = offset;
+            offset += text.len() + 1;
+            let text = text.trim_end_matches('\r');
+
+            let position = match text.find(IGNORE) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            let (before, directive) = text.split_at(position);
+
+            // The directive has to be inside of a comment, otherwise it's probably
+            // just part of a string.
+            if !is_comment(before) {
+                continue;
+            }
+
+            let (target, rest) = if let Some(rest) = directive.strip_prefix(IGNORE_NEXT_LINE) {
+                (line + 1, rest)
+            } else if let Some(rest) = directive.strip_prefix(IGNORE_LINE) {
+                (line, rest)
+            } else {
+                let rest = &directive[IGNORE.len()..];
+                let target = if starts_with_comment(before) {
+                    line + 1
+                } else {
+                    line
+                };
+
+                (target, rest)
+            };
+
+            // Make sure that something like `@statan-ignored` isn't treated as a directive.
+            if rest.starts_with(|c: char| !c.is_whitespace() && c != '*') {
+                continue;
+            }
+
+            let identifiers = parse_identifiers(rest);
+
+            // A bare `@statan-ignore` would be too broad, so it requires identifiers.
+            if identifiers.is_empty()
+                && !directive.starts_with(IGNORE_NEXT_LINE)
+                && !directive.starts_with(IGNORE_LINE)
+            {
+                continue;
+            }
+
+            let start = line_start + position;
+            let end = start + directive.trim_end().trim_end_matches("*/").trim_end().len();
+
+            suppressions.push(Suppression {
+                span: MessageSpan {
+                    line,
+                    column: position + 1,
+                    start,
+                    end,
+                },
+                target,
+                identifiers,
+                used: false,
+            });
+        }
+
+        Self { suppressions }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.suppressions.is_empty()
+    }
+
+    /// Remove every suppressed message from the collector, keeping track of which
+    /// suppressions were used.
+    pub fn apply(&mut self, messages: &mut MessageCollector) {
+        messages.retain(|message| {
+            let mut suppressed = false;
+
+            for suppression in self.suppressions.iter_mut() {
+                if suppression.matches(message.line, &message.identifier) {
+                    suppression.used = true;
+                    suppressed = true;
+                }
+            }
+
+            !suppressed
+        });
+    }
+
+    /// The suppressions that didn't match any message when they were applied.
+    pub fn unmatched(&self) -> impl Iterator<Item = &Suppression> {
+        self.suppressions
+            .iter()
+            .filter(|suppression| !suppression.used)
+    }
+
+    /// Report every suppression that no longer matches a message, so that it can be removed.
+    pub fn report_unmatched(&self, messages: &mut MessageCollector) {
+        for suppression in self.unmatched() {
+            let message = if suppression.identifiers.is_empty() {
+                format!(
+                    "No message to ignore is reported on line {}",
+                    suppression.target
+                )
+            } else {
+                format!(
+                    "No message with identifier {} to ignore is reported on line {}",
+                    suppression.identifiers.join(", "),
+                    suppression.target
+                )
+            };
+
+            messages.warning("ignore.unmatched", message, suppression.span);
+        }
+    }
+}
+
+fn is_comment(before: &str) -> bool {
+    let line_comment = before.rfind("//").or_else(|| before.rfind('#'));
+    let block_comment = before.rfind("/*");
+
+    line_comment.is_some()
+        || block_comment.is_some_and(|position| !before[position..].contains("*/"))
+        || before.trim_start().starts_with('*')
+}
+
+fn starts_with_comment(before: &str) -> bool {
+    let before = before.trim_start();
+
+    before.starts_with("//")
+        || before.starts_with('#')
+        || before.starts_with("/*")
+        || before.starts_with('*')
+}
+
+fn parse_identifiers(rest: &str) -> Vec<String> {
+    let rest = match rest.find("*/") {
+        Some(end) => &rest[..end],
+        None => rest,
+    };
+
+    let mut identifiers = Vec::new();
+
+    for part in rest.split(',') {
+        let identifier = part
+            .trim()
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default();
+
+        if identifier.is_empty()
+            || !identifier
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-')
+        {
+            break;
+        }
+
+        identifiers.push(identifier.to_string());
+
+        // Anything after the first identifier that isn't separated by a comma is
+        // treated as a free-form explanation.
+        if part.trim().contains(char::is_whitespace) {
+            break;
+        }
+    }
+
+    identifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyser::messages::{MessageCollector, MessageSpan};
+
+    use super::Suppressions;
+
+    fn report(messages: &mut MessageCollector, line: usize, identifier: &str) {
+        messages.error(
+            identifier,
+            "Something is wrong",
+            MessageSpan {
+                line,
+                ..MessageSpan::default()
+            },
+        );
+    }
+
+    #[test]
+    fn it_parses_directives() {
+        let suppressions = Suppressions::parse(
+            b"<?php
+// @statan-ignore-next-line
+foo();
+bar(); // @statan-ignore-line function.notFound, argument.type
+// @statan-ignore function.notFound because it's defined at runtime
+baz(); /* @statan-ignore argument.type */
+",
+        );
+        let parsed = suppressions
+            .suppressions
+            .iter()
+            .map(|suppression| (suppression.target, suppression.identifiers.clone()))
+            .collect::<Vec<(usize, Vec<String>)>>();
+
+        assert_eq!(
+            parsed,
+            vec![
+                (3, vec![]),
+                (4, vec!["function.notFound".into(), "argument.type".into()]),
+                (6, vec!["function.notFound".into()]),
+                (6, vec!["argument.type".into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_skips_directives_outside_of_comments() {
+        let suppressions = Suppressions::parse(
+            b"<?php
+echo '@statan-ignore-line';
+// @statan-ignored-line
+// @statan-ignore
+",
+        );
+
+        assert!(suppressions.is_empty());
+    }
+
+    #[test]
+    fn it_removes_suppressed_messages_and_reports_unmatched_ones() {
+        let mut suppressions = Suppressions::parse(
+            b"<?php
+// @statan-ignore function.notFound
+foo();
+// @statan-ignore-next-line
+bar();
+",
+        );
+
+        let mut messages = MessageCollector::new("test.php".to_string());
+        report(&mut messages, 3, "function.notFound");
+        report(&mut messages, 3, "argument.type");
+        suppressions.apply(&mut messages);
+
+        let identifiers = messages
+            .iter()
+            .map(|message| message.identifier.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(identifiers, vec!["argument.type"]);
+
+        let unmatched = suppressions
+            .unmatched()
+            .map(|suppression| suppression.target)
+            .collect::<Vec<usize>>();
+        assert_eq!(unmatched, vec![5]);
+    }
+
+    #[test]
+    fn it_applies_to_messages_merged_after_the_analysis() {
+        let mut suppressions = Suppressions::parse(
+            b"<?php
+// @statan-ignore function.unused
+function helper() {}
+",
+        );
+
+        let mut messages = MessageCollector::new("test.php".to_string());
+        suppressions.apply(&mut messages);
+        suppressions.report_unmatched(&mut messages);
+        assert_eq!(messages.len(), 1);
+        messages.set_suppressions(suppressions);
+
+        let mut project_messages = MessageCollector::new("test.php".to_string());
+        report(&mut project_messages, 3, "function.unused");
+        messages.merge(project_messages);
+
+        assert!(messages.is_empty());
+    }
+}
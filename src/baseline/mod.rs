@@ -0,0 +1,218 @@
+use std::{
+    collections::BTreeMap,
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    analyser::messages::{MessageCollector, MessageSeverity},
+    project::normalise,
+};
+
+pub const BASELINE_FILE: &str = "statan-baseline.toml";
+
+/// A snapshot of the messages reported for a project, so that existing messages
+/// can be ignored and only new ones are reported.
+///
+/// ```toml
+/// [[errors]]
+/// file = "src/Legacy/Handler.php"
+/// identifier = "function.notFound"
+/// message = "Function `legacy_helper` not found"
+/// count = 2
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Baseline {
+    errors: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaselineEntry {
+    /// The file the messages were reported in, relative to the baseline file.
+    pub file: PathBuf,
+    pub identifier: String,
+    pub message: String,
+    /// The number of times the message was reported in the file.
+    pub count: usize,
+}
+
+impl Baseline {
+    /// Create a baseline from the given messages. Notes are left out, since they
+    /// don't cause the analysis to fail.
+    pub fn generate(message_collections: &[MessageCollector]) -> Self {
+        let mut counts: BTreeMap<(PathBuf, String, String), usize> = BTreeMap::new();
+
+        for messages in message_collections {
+            let file = normalise(Path::new(messages.get_file()));
+
+            for message in messages.iter() {
+                if message.severity == MessageSeverity::Note {
+                    continue;
+                }
+
+                *counts
+                    .entry((
+                        file.clone(),
+                        message.identifier.clone(),
+                        message.message.clone(),
+                    ))
+                    .or_default() += 1;
+            }
+        }
+
+        Self {
+            errors: counts
+                .into_iter()
+                .map(|((file, identifier, message), count)| BaselineEntry {
+                    file,
+                    identifier,
+                    message,
+                    count,
+                })
+                .collect(),
+        }
+    }
+
+    /// Load the baseline file at the given path. Paths inside of the file are
+    /// resolved relative to the directory that contains it.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = read_to_string(path)
+            .map_err(|error| format!("failed to read {}: {error}", path.display()))?;
+
+        let mut baseline: Baseline = toml::from_str(&contents)
+            .map_err(|error| format!("failed to parse {}: {error}", path.display()))?;
+
+        let root = path.parent().unwrap_or(Path::new("."));
+        for entry in baseline.errors.iter_mut() {
+            entry.file = normalise(&root.join(&entry.file));
+        }
+
+        Ok(baseline)
+    }
+
+    /// Write the baseline to the given path, with paths relative to the directory
+    /// that will contain it.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let root = normalise(path.parent().unwrap_or(Path::new(".")));
+        let mut baseline = self.clone();
+
+        for entry in baseline.errors.iter_mut() {
+            if let Ok(relative) = entry.file.strip_prefix(&root) {
+                entry.file = relative.to_path_buf();
+            }
+        }
+
+        let contents = toml::to_string(&baseline).map_err(|error| error.to_string())?;
+
+        write(path, contents)
+            .map_err(|error| format!("failed to write {}: {error}", path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.iter().map(|entry| entry.count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every message that is in the baseline from the given collections.
+    /// The baseline entries that didn't match as many messages as they were
+    /// expected to are returned, with their count set to the number of messages
+    /// that are missing. Entries for files that exist but weren't analysed aren't
+    /// returned, since their messages couldn't have been reported.
+    pub fn apply(&self, message_collections: &mut [MessageCollector]) -> Vec<BaselineEntry> {
+        let mut remaining = self.errors.clone();
+        let mut analysed = Vec::new();
+
+        for messages in message_collections.iter_mut() {
+            let file = normalise(Path::new(messages.get_file()));
+            analysed.push(file.clone());
+
+            messages.retain(|message| {
+                let entry = remaining.iter_mut().find(|entry| {
+                    entry.count > 0
+                        && entry.file == file
+                        && entry.identifier == message.identifier
+                        && entry.message == message.message
+                });
+
+                match entry {
+                    Some(entry) => {
+                        entry.count -= 1;
+                        false
+                    }
+                    None => true,
+                }
+            });
+        }
+
+        // The entries of deleted files are stale too, even though they weren't analysed.
+        remaining.retain(|entry| {
+            entry.count > 0 && (analysed.contains(&entry.file) || !entry.file.exists())
+        });
+        remaining
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::analyser::messages::{MessageCollector, MessageSpan};
+
+    use super::Baseline;
+
+    fn messages(file: &str, count: usize) -> MessageCollector {
+        let mut messages = MessageCollector::new(file.to_string());
+
+        for _ in 0..count {
+            messages.error(
+                "function.notFound",
+                "Function `helper` not found",
+                MessageSpan::default(),
+            );
+        }
+
+        messages
+    }
+
+    #[test]
+    fn it_removes_the_messages_in_the_baseline() {
+        let baseline = Baseline::generate(&[messages("src/a.php", 2)]);
+        let mut collections = vec![messages("./src/a.php", 3)];
+
+        let stale = baseline.apply(&mut collections);
+
+        assert_eq!(collections[0].len(), 1);
+        assert!(stale.is_empty());
+    }
+
+    #[test]
+    fn it_returns_the_entries_that_no_longer_occur() {
+        let baseline = Baseline::generate(&[messages("src/a.php", 2)]);
+        let mut collections = vec![messages("src/a.php", 1)];
+
+        let stale = baseline.apply(&mut collections);
+
+        assert!(collections[0].is_empty());
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].file, PathBuf::from("src/a.php"));
+        assert_eq!(stale[0].count, 1);
+    }
+
+    #[test]
+    fn it_ignores_the_entries_of_files_that_were_not_analysed() {
+        let baseline = Baseline::generate(&[messages("src/lib.rs", 1), messages("src/main.rs", 1)]);
+        let mut collections = vec![messages("src/lib.rs", 1)];
+
+        let stale = baseline.apply(&mut collections);
+
+        assert!(collections[0].is_empty());
+        assert!(stale.is_empty());
+    }
+}
@@ -1,83 +1,368 @@
-use std::fs::{metadata, read};
+use std::{
+    env::current_dir,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+};
 
 use colored::*;
 use indicatif::ProgressBar;
-use prettytable::{row, Table};
-use statan::{analyser::Analyser, definitions::collector::DefinitionCollector, rules};
+use statan::{
+    analyser::messages::MessageSeverity,
+    baseline::Baseline,
+    config::Configuration,
+    formatters::ErrorFormat,
+    git,
+    project::patterns::PathPattern,
+    runner::{LoadedProject, Report, Runner},
+};
 
 use crate::AnalyseCommand;
 
-pub fn run(args: AnalyseCommand) {
-    let files = discoverer::discover(&["php"], &["."]).unwrap();
-    let mut collector = DefinitionCollector::new();
+use super::watch::{self, WatchState};
 
-    println!("{}", "> Discovering project definitions...".yellow());
+/// The exit code used when the analysis reported errors.
+const EXIT_ERRORS: u8 = 1;
+/// The exit code used when Statan itself failed, e.g. because of invalid configuration.
+pub(crate) const EXIT_FAILURE: u8 = 2;
 
-    for file in files {
-        let contents = std::fs::read(&file).unwrap();
-        let parse_result = pxp_parser::parse(&contents);
-        if parse_result.is_err() {
-            println!("failed to parse {}", &file.to_str().unwrap());
-            println!("{}", parse_result.err().unwrap());
-            continue;
+pub fn run(args: AnalyseCommand) -> ExitCode {
+    let (includes, excludes) = match parse_patterns(&args.paths, &args.exclude) {
+        Ok(patterns) => patterns,
+        Err(error) => {
+            eprintln!("{}", error.red());
+            return ExitCode::from(EXIT_FAILURE);
         }
-        let mut ast = parse_result.unwrap();
-        collector.scan(&mut ast);
+    };
+
+    // The project is found from the paths that are analysed, so that analysing another
+    // project doesn't scan the working directory for definitions.
+    let directory = match includes.first() {
+        Some(pattern) => pattern.base(),
+        None => PathBuf::from("."),
+    };
+    let directory = if directory.is_file() {
+        directory
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    } else {
+        directory
+    };
+
+    let mut configuration = match load_configuration(args.config.as_deref(), &directory) {
+        Ok(configuration) => configuration,
+        Err(error) => {
+            eprintln!("{}", error.red());
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    if args.level.is_some() {
+        configuration.level = args.level;
+    }
+
+    let mut runner = Runner::new(configuration);
+    runner.set_verbose(args.verbose);
+    if let Some(php_version) = args.php_version {
+        runner.set_php_version(php_version);
+    }
+
+    // A baseline generated from some of the files would lose the entries of the others.
+    if args.generate_baseline.is_some() && (!args.paths.is_empty() || args.changed_since.is_some())
+    {
+        eprintln!(
+            "{}",
+            "--generate-baseline analyses the whole project, so it can't be used with paths or --changed-since"
+                .red()
+        );
+        return ExitCode::from(EXIT_FAILURE);
     }
 
-    println!("{}", "> Analysing project...".yellow());
-
-    let collection = collector.collect();
-
-    // std::fs::write("./collection.json", serde_json::to_string_pretty(&collection).unwrap()).unwrap();
-
-    let mut analyser = Analyser::new(collection);
-    analyser.add_rule(Box::new(rules::valid_assignment::ValidAssignmentRule));
-    analyser.add_rule(Box::new(rules::dump_type::DumpTypeRule));
-    analyser.add_rule(Box::new(rules::valid_function::ValidFunctionRule));
-    analyser.add_rule(Box::new(rules::valid_class::ValidClassRule));
-    analyser.add_rule(Box::new(rules::valid_static_call::ValidStaticCallRule));
-    analyser.add_rule(Box::new(rules::valid_this_call::ValidThisCallRule));
-    analyser.add_rule(Box::new(
-        rules::abstract_method_in_non_abstract_class::AbstractMethodInNonAbstractClassRule,
-    ));
-    analyser.add_rule(Box::new(
-        rules::call_private_through_static::CallPrivateThroughStaticRule,
-    ));
-    analyser.add_rule(Box::new(rules::function_definition::FunctionDefinitionRule));
-    analyser.add_rule(Box::new(
-        rules::valid_arithmetic_operation::ValidArithmeticOperationRule,
-    ));
-
-    let mut message_collections = Vec::new();
-    let metadata = metadata(&args.file).unwrap();
-
-    if metadata.is_dir() {
-        let files = discoverer::discover(&["php"], &[&args.file]).unwrap();
-        let progress_bar = ProgressBar::new(files.len() as u64);
-        for file in files {
-            let contents = read(&file).unwrap();
-            let messages = analyser.analyse(file.to_str().unwrap().to_string(), &contents);
-            message_collections.push(messages);
-            progress_bar.inc(1);
+    // Make sure that the configured rules exist before doing any work.
+    if let Err(error) = runner.rules() {
+        eprintln!("{}", error.red());
+        return ExitCode::from(EXIT_FAILURE);
+    }
+
+    // Paths passed on the command line take precedence over the configured paths.
+    let filter = match runner.path_filter(includes, excludes) {
+        Ok(filter) => filter,
+        Err(error) => {
+            eprintln!("{}", error.red());
+            return ExitCode::from(EXIT_FAILURE);
         }
-        progress_bar.finish();
-    } else {
-        let contents = read(&args.file).unwrap();
-        let messages = analyser.analyse(args.file, &contents);
-        message_collections.push(messages);
+    };
+
+    // Definitions are still collected from the whole project, but only the changed
+    // files are analysed.
+    let changed_files = match &args.changed_since {
+        Some(reference) => match git::changed_files(reference) {
+            Ok(changed_files) => Some(changed_files),
+            Err(error) => {
+                eprintln!("{}", error.red());
+                return ExitCode::from(EXIT_FAILURE);
+            }
+        },
+        None => None,
+    };
+
+    let is_analysed = |path: &Path| {
+        filter.matches(path)
+            && changed_files
+                .as_ref()
+                .is_none_or(|changed_files| changed_files.contains_key(path))
+    };
+
+    eprintln!("{}", "> Discovering project definitions...".yellow());
+
+    let LoadedProject {
+        mut project,
+        definitions,
+        warnings,
+    } = match runner.load_project(&filter.base_paths()) {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            eprintln!("{}", error.red());
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    for warning in warnings.iter() {
+        eprintln!("{}", format!("> {warning}").red());
+    }
+
+    for file in project.files().iter().filter(|file| !file.is_scan_only()) {
+        if let Some(error) = file.parse_error() {
+            eprintln!("failed to parse {}", file.path().display());
+            eprintln!("{error}");
+        }
+    }
+
+    // The definitions have been collected, so we only need to hold on to the ASTs
+    // of the files that are actually going to be analysed.
+    project.retain_asts(is_analysed);
+
+    eprintln!("{}", "> Analysing project...".yellow());
+
+    let definitions = Arc::new(definitions);
+    let analysed_files = project
+        .files()
+        .iter()
+        .filter(|file| !file.is_scan_only() && is_analysed(file.path()))
+        .count();
+
+    let progress_bar = ProgressBar::new(analysed_files as u64);
+    let mut message_collections = project.analyse(
+        is_analysed,
+        || runner.create_analyser(definitions.clone()),
+        |_| progress_bar.inc(1),
+    );
+    progress_bar.finish();
+
+    // Watch mode runs the project rules again whenever a file changes, so it needs
+    // the messages and collected data as they came out of the analysis.
+    let analysed = args.watch.then(|| message_collections.clone());
+
+    // Project rules need the data from every file, so they can't be run when only the
+    // changed files were analysed.
+    if changed_files.is_none() {
+        runner.run_project_rules(&definitions, &mut message_collections);
+    }
+
+    if let Some(path) = &args.generate_baseline {
+        runner.filter_messages(None, &mut message_collections);
+
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => runner.configuration().baseline(),
+        };
+
+        let baseline = Baseline::generate(&message_collections);
+        return match baseline.save(&path) {
+            Ok(()) => {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "> Baseline with {} errors written to {}",
+                        baseline.len(),
+                        path.display()
+                    )
+                    .green()
+                );
+                ExitCode::SUCCESS
+            }
+            Err(error) => {
+                eprintln!("{}", error.red());
+                ExitCode::from(EXIT_FAILURE)
+            }
+        };
     }
 
-    for messages in message_collections {
-        if messages.iter().len() == 0 {
-            return;
+    let baseline = match runner.load_baseline() {
+        Ok(baseline) => baseline,
+        Err(error) => {
+            eprintln!("{}", error.red());
+            return ExitCode::from(EXIT_FAILURE);
         }
+    };
+
+    let stale_baseline_entries =
+        runner.filter_messages(baseline.as_ref(), &mut message_collections);
 
-        let mut table = Table::new();
-        table.add_row(row!["Line", messages.get_file()]);
-        for message in messages.iter() {
-            table.add_row(row![message.line, message.message]);
+    // The baseline is matched against every message in the changed files, otherwise
+    // the entries for the lines that didn't change would look stale.
+    if let (Some(changed_files), true) = (&changed_files, args.changed_lines) {
+        for messages in message_collections.iter_mut() {
+            if let Some(lines) = changed_files.get(Path::new(messages.get_file())) {
+                messages.retain(|message| lines.contains(message.line));
+            }
         }
-        table.printstd();
+    }
+
+    let report = Report {
+        messages: message_collections,
+        stale_baseline_entries,
+        warnings,
+    };
+
+    for entry in report.stale_baseline_entries.iter() {
+        eprintln!(
+            "{}",
+            format!(
+                "> Baseline entry \"{}\" ({}) in {} no longer occurs {} time(s), it can be removed from {}",
+                entry.message,
+                entry.identifier,
+                entry.file.display(),
+                entry.count,
+                runner.configuration().baseline().display()
+            )
+            .yellow()
+        );
+    }
+
+    print!("{}", args.error_format.format(&report.messages));
+
+    let errors = report.count(MessageSeverity::Error);
+    let warnings = report.count(MessageSeverity::Warning);
+    let failing_files = report.failing_files(args.fail_on);
+    // Notes are only counted when they fail the analysis.
+    let notes = match args.fail_on {
+        MessageSeverity::Note => report.count(MessageSeverity::Note),
+        _ => 0,
+    };
+
+    let summary = if errors == 0 && warnings == 0 && notes == 0 {
+        "No errors found".green()
+    } else {
+        let files = report
+            .messages
+            .iter()
+            .filter(|messages| !messages.is_empty())
+            .count();
+
+        let counts = if notes > 0 {
+            format!(
+                "{}, {} and {}",
+                plural(errors, "error"),
+                plural(warnings, "warning"),
+                plural(notes, "note")
+            )
+        } else {
+            format!(
+                "{} and {}",
+                plural(errors, "error"),
+                plural(warnings, "warning")
+            )
+        };
+
+        let summary = format!("Found {counts} in {}", plural(files, "file"));
+
+        if failing_files > 0 {
+            summary.red()
+        } else {
+            summary.yellow()
+        }
+    };
+
+    // Machine-readable output shouldn't be mixed up with the summary.
+    if args.error_format == ErrorFormat::Table {
+        println!("\n{summary}");
+    } else {
+        eprintln!("{summary}");
+    }
+
+    if args.watch {
+        return watch::run(WatchState {
+            project,
+            definitions,
+            runner: &runner,
+            baseline: baseline.as_ref(),
+            is_analysed,
+            analysed: analysed.unwrap_or_default(),
+            results: report.messages,
+            run_project_rules: changed_files.is_none(),
+        });
+    }
+
+    // A file that Statan crashed on wasn't fully analysed, so the results can't be trusted.
+    let internal_errors = report
+        .messages
+        .iter()
+        .flat_map(|messages| messages.iter())
+        .any(|message| message.identifier == "internal.error");
+
+    if internal_errors {
+        ExitCode::from(EXIT_FAILURE)
+    } else if failing_files > 0 {
+        ExitCode::from(EXIT_ERRORS)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Parse the patterns of the paths to analyse and the paths to exclude.
+fn parse_patterns(
+    paths: &[String],
+    excludes: &[String],
+) -> Result<(Vec<PathPattern>, Vec<PathPattern>), String> {
+    let parse = |patterns: &[String]| {
+        patterns
+            .iter()
+            .map(|pattern| PathPattern::new(pattern))
+            .collect::<Result<Vec<PathPattern>, String>>()
+    };
+
+    Ok((parse(paths)?, parse(excludes)?))
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Load the given configuration file, or the nearest one to the given directory.
+/// Without a configuration file, the defaults are used for the project that
+/// contains the directory.
+pub(crate) fn load_configuration(
+    config: Option<&str>,
+    directory: &Path,
+) -> Result<Configuration, String> {
+    let directory = directory
+        .canonicalize()
+        .or_else(|_| current_dir())
+        .map_err(|error| error.to_string())?;
+
+    let path = match config {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Configuration::discover(&directory),
+    };
+
+    match path {
+        Some(path) => Configuration::load(&path),
+        None => Ok(Configuration::for_directory(&directory)),
     }
 }
@@ -0,0 +1,715 @@
+use std::{
+    collections::HashMap,
+    env::current_dir,
+    error::Error,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{Arc, Mutex},
+};
+
+use colored::*;
+use lsp_server::{Connection, Message as LspMessage, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{GotoDefinition, HoverRequest, Request as _, WorkspaceSymbolRequest},
+    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams, HoverProviderCapability, Location,
+    MarkupContent, MarkupKind, NumberOrString, OneOf, Position, PublishDiagnosticsParams, Range,
+    ServerCapabilities, SymbolInformation, SymbolKind, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url, WorkspaceSymbolParams,
+};
+use pxp_parser::{
+    downcast::downcast,
+    lexer::{byte_string::ByteString, token::Span},
+    node::Node,
+    parse,
+    parser::ast::{
+        identifiers::{Identifier, SimpleIdentifier},
+        variables::{SimpleVariable, Variable},
+        Expression, FunctionCallExpression, MethodCallExpression, NewExpression,
+        StaticMethodCallExpression,
+    },
+};
+use statan::{
+    analyser::{
+        context::Context,
+        messages::{Message, MessageCollector, MessageSeverity},
+    },
+    definitions::{
+        collection::DefinitionCollection, collector::DefinitionCollector,
+        location::DefinitionLocation,
+    },
+    project::normalise,
+    rules::Rule,
+    runner::Runner,
+    shared::types::Type,
+};
+
+use crate::LspCommand;
+
+use super::analyse::{load_configuration, EXIT_FAILURE};
+
+pub fn run(args: LspCommand) -> ExitCode {
+    let configuration = match load_configuration(args.config.as_deref(), Path::new(".")) {
+        Ok(configuration) => configuration,
+        Err(error) => {
+            eprintln!("{}", error.red());
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    let mut runner = Runner::new(configuration);
+    if let Some(php_version) = args.php_version {
+        runner.set_php_version(php_version);
+    }
+
+    if let Err(error) = runner.rules() {
+        eprintln!("{}", error.red());
+        return ExitCode::from(EXIT_FAILURE);
+    }
+
+    // The language server communicates over stdout, so everything else goes to stderr.
+    let definitions = match runner.load_project(&runner.configuration().paths) {
+        Ok(loaded) => {
+            for warning in loaded.warnings {
+                eprintln!("{}", format!("> {warning}").red());
+            }
+
+            loaded.definitions
+        }
+        Err(error) => {
+            eprintln!("{}", error.red());
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    match serve(Server::new(runner, definitions)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error.to_string().red());
+            ExitCode::from(EXIT_FAILURE)
+        }
+    }
+}
+
+fn serve(mut server: Server) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    })?;
+
+    connection.initialize(capabilities)?;
+
+    eprintln!("{}", "> Language server started".green());
+
+    for message in &connection.receiver {
+        match message {
+            LspMessage::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+
+                connection
+                    .sender
+                    .send(LspMessage::Response(server.handle_request(request)))?;
+            }
+            LspMessage::Notification(notification) => {
+                for notification in server.handle_notification(notification) {
+                    connection
+                        .sender
+                        .send(LspMessage::Notification(notification))?;
+                }
+            }
+            LspMessage::Response(_) => {}
+        }
+    }
+
+    io_threads.join()?;
+
+    Ok(())
+}
+
+/// The state of the language server: the project's definitions and the contents of
+/// the documents that are open in the editor, which may not have been saved yet.
+struct Server {
+    runner: Runner,
+    definitions: Arc<DefinitionCollection>,
+    documents: HashMap<Url, String>,
+}
+
+impl Server {
+    fn new(runner: Runner, definitions: DefinitionCollection) -> Self {
+        Self {
+            runner,
+            definitions: Arc::new(definitions),
+            documents: HashMap::new(),
+        }
+    }
+
+    fn handle_request(&mut self, request: Request) -> Response {
+        let id = request.id.clone();
+        let method = request.method.clone();
+
+        let result = match method.as_str() {
+            HoverRequest::METHOD => parse_params::<HoverParams>(request)
+                .map(|params| serde_json::to_value(self.hover(params))),
+            GotoDefinition::METHOD => parse_params::<GotoDefinitionParams>(request)
+                .map(|params| serde_json::to_value(self.definition(params))),
+            WorkspaceSymbolRequest::METHOD => parse_params::<WorkspaceSymbolParams>(request)
+                .map(|params| serde_json::to_value(self.workspace_symbols(params))),
+            _ => return method_not_found(id, &method),
+        };
+
+        match result {
+            Ok(Ok(value)) => Response::new_ok(id, value),
+            Ok(Err(error)) | Err(error) => Response::new_err(
+                id,
+                lsp_server::ErrorCode::InvalidParams as i32,
+                error.to_string(),
+            ),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Vec<Notification> {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                match serde_json::from_value::<DidOpenTextDocumentParams>(notification.params) {
+                    Ok(params) => {
+                        let uri = params.text_document.uri;
+                        self.documents
+                            .insert(uri.clone(), params.text_document.text);
+                        uri
+                    }
+                    Err(_) => return Vec::new(),
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                match serde_json::from_value::<DidChangeTextDocumentParams>(notification.params) {
+                    Ok(mut params) => {
+                        // Documents are synchronised in full, so the last change is the whole document.
+                        let uri = params.text_document.uri;
+                        if let Some(change) = params.content_changes.pop() {
+                            self.documents.insert(uri.clone(), change.text);
+                        }
+                        uri
+                    }
+                    Err(_) => return Vec::new(),
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                match serde_json::from_value::<DidSaveTextDocumentParams>(notification.params) {
+                    Ok(params) => {
+                        let uri = params.text_document.uri;
+                        if let Some(text) = params.text {
+                            self.documents.insert(uri.clone(), text);
+                        }
+                        uri
+                    }
+                    Err(_) => return Vec::new(),
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                match serde_json::from_value::<DidCloseTextDocumentParams>(notification.params) {
+                    Ok(params) => {
+                        let uri = params.text_document.uri;
+                        self.documents.remove(&uri);
+
+                        return vec![publish_diagnostics(uri, Vec::new())];
+                    }
+                    Err(_) => return Vec::new(),
+                }
+            }
+            _ => return Vec::new(),
+        };
+
+        self.update_definitions(&uri);
+
+        let diagnostics = self.diagnostics(&uri);
+        vec![publish_diagnostics(uri, diagnostics)]
+    }
+
+    /// Replace the definitions from the given document with the ones in its current
+    /// contents, so that other documents see the changes before they're saved.
+    fn update_definitions(&mut self, uri: &Url) {
+        let (path, contents) = match (document_path(uri), self.documents.get(uri)) {
+            (Some(path), Some(contents)) => (path, contents),
+            _ => return,
+        };
+
+        // A document with syntax errors keeps its previous definitions.
+        let mut ast = match parse(contents.as_bytes()) {
+            Ok(ast) => ast,
+            Err(_) => return,
+        };
+
+        let mut collector = DefinitionCollector::for_file(path.clone());
+        collector.scan(&mut ast);
+
+        let definitions = Arc::make_mut(&mut self.definitions);
+        definitions.remove_file(&path);
+        definitions.merge(collector.collect());
+    }
+
+    fn diagnostics(&self, uri: &Url) -> Vec<Diagnostic> {
+        let (path, contents) = match (document_path(uri), self.documents.get(uri)) {
+            (Some(path), Some(contents)) => (path, contents),
+            _ => return Vec::new(),
+        };
+
+        if self.runner.configuration().is_excluded(&path) {
+            return Vec::new();
+        }
+
+        let messages = self.analyse(&path, contents, Vec::new());
+
+        messages
+            .iter()
+            .filter(|message| !self.runner.configuration().is_ignored(&path, message))
+            .map(|message| diagnostic(contents, message))
+            .collect()
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let inspection = self.inspect(&position.text_document.uri, position.position)?;
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```php\n{}\n```", inspection.type_?),
+            }),
+            range: None,
+        })
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let inspection = self.inspect(&position.text_document.uri, position.position)?;
+
+        self.location(&inspection.definition?, &mut HashMap::new())
+            .map(GotoDefinitionResponse::Scalar)
+    }
+
+    /// The location of a definition. Positions are counted in UTF-16 code units, so
+    /// the definition's byte offset is converted using the contents of its file, which
+    /// are read at most once for each file.
+    fn location(
+        &self,
+        location: &DefinitionLocation,
+        files: &mut HashMap<PathBuf, Option<String>>,
+    ) -> Option<Location> {
+        let file = location.file.as_ref()?;
+        let file = if file.is_absolute() {
+            file.clone()
+        } else {
+            current_dir().ok()?.join(file)
+        };
+        let uri = Url::from_file_path(&file).ok()?;
+
+        let contents = match self.documents.get(&uri) {
+            Some(contents) => Some(contents.as_str()),
+            None => files
+                .entry(file.clone())
+                .or_insert_with(|| read_to_string(&file).ok())
+                .as_deref(),
+        };
+
+        let position = match contents {
+            Some(contents) => position_at(contents, location.offset),
+            None => Position::new(
+                location.line.saturating_sub(1) as u32,
+                location.column.saturating_sub(1) as u32,
+            ),
+        };
+
+        Some(Location::new(uri, Range::new(position, position)))
+    }
+
+    // `SymbolInformation::deprecated` is deprecated in favour of tags, but it still has to be set.
+    #[allow(deprecated)]
+    fn workspace_symbols(&self, params: WorkspaceSymbolParams) -> Vec<SymbolInformation> {
+        let query = params.query.to_lowercase();
+        let definitions = &self.definitions;
+        let mut files = HashMap::new();
+
+        let symbols = definitions
+            .functions()
+            .iter()
+            .map(|function| (&function.name, SymbolKind::FUNCTION, &function.location))
+            .chain(
+                definitions
+                    .classes()
+                    .iter()
+                    .map(|class| (&class.name, SymbolKind::CLASS, &class.location)),
+            )
+            .chain(
+                definitions
+                    .interfaces()
+                    .iter()
+                    .map(|interface| (&interface.name, SymbolKind::INTERFACE, &interface.location)),
+            )
+            .chain(
+                definitions
+                    .traits()
+                    .iter()
+                    .map(|trait_| (&trait_.name, SymbolKind::CLASS, &trait_.location)),
+            )
+            .chain(
+                definitions
+                    .enums()
+                    .iter()
+                    .map(|enum_| (&enum_.name, SymbolKind::ENUM, &enum_.location)),
+            );
+
+        symbols
+            .filter(|(name, _, _)| name.to_string().to_lowercase().contains(&query))
+            .filter_map(|(name, kind, definition_location)| {
+                Some(SymbolInformation {
+                    name: name.to_string().trim_start_matches('\\').to_string(),
+                    kind,
+                    tags: None,
+                    deprecated: None,
+                    location: self.location(definition_location, &mut files)?,
+                    container_name: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Analyse the document and find out what's at the given position.
+    fn inspect(&self, uri: &Url, position: Position) -> Option<Inspection> {
+        let path = document_path(uri)?;
+        let contents = self.documents.get(uri)?;
+
+        let inspection = Arc::new(Mutex::new(None));
+        let rule = InspectRule {
+            offset: offset_at(contents, position),
+            inspection: inspection.clone(),
+        };
+
+        self.analyse(&path, contents, vec![Box::new(rule)]);
+
+        let inspection = inspection.lock().unwrap().take();
+        inspection
+    }
+
+    /// Analyse the contents of a document with the configured rules, plus any extra
+    /// rules. The extra rules run after the configured ones, so that they can see the
+    /// variable types that the configured rules have entered into the context.
+    fn analyse(
+        &self,
+        path: &Path,
+        contents: &str,
+        extra_rules: Vec<Box<dyn Rule>>,
+    ) -> MessageCollector {
+        let mut analyser = self.runner.create_analyser(self.definitions.clone());
+
+        for rule in extra_rules {
+            analyser.add_rule(rule);
+        }
+
+        analyser.analyse(path.to_string_lossy().to_string(), contents.as_bytes())
+    }
+}
+
+/// What was found at a position in a document.
+#[derive(Debug, Default)]
+struct Inspection {
+    type_: Option<Type>,
+    definition: Option<DefinitionLocation>,
+}
+
+/// Finds the variable, call or class name at a given byte offset. It's added to an
+/// analyser so that it sees the same context as the other rules.
+#[derive(Debug)]
+struct InspectRule {
+    offset: usize,
+    inspection: Arc<Mutex<Option<Inspection>>>,
+}
+
+impl InspectRule {
+    fn contains(&self, span: &Span, name: &ByteString) -> bool {
+        span.position <= self.offset && self.offset <= span.position + name.len()
+    }
+
+    fn found(&self, inspection: Inspection) {
+        *self.inspection.lock().unwrap() = Some(inspection);
+    }
+}
+
+impl Rule for InspectRule {
+    fn name(&self) -> &'static str {
+        "InspectRule"
+    }
+
+    fn should_run(&self, node: &dyn Node) -> bool {
+        downcast::<SimpleVariable>(node).is_some()
+            || downcast::<FunctionCallExpression>(node).is_some()
+            || downcast::<NewExpression>(node).is_some()
+            || downcast::<MethodCallExpression>(node).is_some()
+            || downcast::<StaticMethodCallExpression>(node).is_some()
+    }
+
+    fn enter_node(
+        &mut self,
+        node: &mut dyn Node,
+        definitions: &DefinitionCollection,
+        _messages: &mut MessageCollector,
+        context: &mut Context,
+    ) {
+        if let Some(variable) = downcast::<SimpleVariable>(node) {
+            if self.contains(&variable.span, &variable.name) {
+                let expression = Expression::Variable(Variable::SimpleVariable(variable.clone()));
+
+                self.found(Inspection {
+                    type_: Some(context.get_type(&expression, definitions)),
+                    definition: None,
+                });
+            }
+        }
+
+        if let Some(call) = downcast::<FunctionCallExpression>(node) {
+            if let Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                span,
+                value,
+            })) = call.target.as_ref()
+            {
+                if self.contains(span, value) {
+                    let expression = Expression::FunctionCall(call.clone());
+
+                    self.found(Inspection {
+                        type_: Some(context.get_type(&expression, definitions)),
+                        definition: definitions
+                            .get_function(value, context)
+                            .map(|function| function.location.clone()),
+                    });
+                }
+            }
+        }
+
+        if let Some(new) = downcast::<NewExpression>(node) {
+            if let Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                span,
+                value,
+            })) = new.target.as_ref()
+            {
+                if self.contains(span, value) {
+                    let expression = Expression::New(new.clone());
+
+                    self.found(Inspection {
+                        type_: Some(context.get_type(&expression, definitions)),
+                        definition: definitions
+                            .get_class(value, context)
+                            .map(|class| class.location.clone()),
+                    });
+                }
+            }
+        }
+
+        if let Some(call) = downcast::<MethodCallExpression>(node) {
+            let is_this = matches!(
+                call.target.as_ref(),
+                Expression::Variable(Variable::SimpleVariable(SimpleVariable { name, .. }))
+                    if name == &ByteString::from(b"$this")
+            );
+
+            if let Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                span,
+                value,
+            })) = call.method.as_ref()
+            {
+                if is_this && context.is_in_class() && self.contains(span, value) {
+                    let method = definitions
+                        .get_class(context.classish_context(), context)
+                        .and_then(|class| class.get_method(value, definitions, context));
+
+                    self.found(Inspection {
+                        type_: method.and_then(|method| method.return_type.clone()),
+                        definition: method.map(|method| method.location.clone()),
+                    });
+                }
+            }
+        }
+
+        if let Some(call) = downcast::<StaticMethodCallExpression>(node) {
+            let class_name = match call.target.as_ref() {
+                Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                    span,
+                    value,
+                })) => {
+                    if self.contains(span, value) {
+                        self.found(Inspection {
+                            type_: None,
+                            definition: definitions
+                                .get_class(value, context)
+                                .map(|class| class.location.clone()),
+                        });
+                    }
+
+                    value.clone()
+                }
+                Expression::Self_ | Expression::Static if context.is_in_class() => {
+                    context.classish_context().clone()
+                }
+                _ => return,
+            };
+
+            if let Identifier::SimpleIdentifier(SimpleIdentifier { span, value }) = &call.method {
+                if self.contains(span, value) {
+                    let method = definitions
+                        .get_class(&class_name, context)
+                        .and_then(|class| class.get_method(value, definitions, context));
+
+                    self.found(Inspection {
+                        type_: method.and_then(|method| method.return_type.clone()),
+                        definition: method.map(|method| method.location.clone()),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn parse_params<P: serde::de::DeserializeOwned>(request: Request) -> Result<P, serde_json::Error> {
+    serde_json::from_value(request.params)
+}
+
+fn method_not_found(id: RequestId, method: &str) -> Response {
+    Response::new_err(
+        id,
+        lsp_server::ErrorCode::MethodNotFound as i32,
+        format!("unsupported request {method}"),
+    )
+}
+
+fn publish_diagnostics(uri: Url, diagnostics: Vec<Diagnostic>) -> Notification {
+    Notification::new(
+        PublishDiagnostics::METHOD.to_string(),
+        PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        },
+    )
+}
+
+fn diagnostic(contents: &str, message: &Message) -> Diagnostic {
+    let severity = match message.severity {
+        MessageSeverity::Error => DiagnosticSeverity::ERROR,
+        MessageSeverity::Warning => DiagnosticSeverity::WARNING,
+        MessageSeverity::Note => DiagnosticSeverity::INFORMATION,
+    };
+
+    // When only the start of the code is known, the word at that position is highlighted.
+    let end = if message.end > message.start {
+        message.end
+    } else {
+        message.start
+            + contents.as_bytes()[message.start.min(contents.len())..]
+                .iter()
+                .take_while(|byte| {
+                    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'$' | b'\\')
+                })
+                .count()
+    };
+
+    Diagnostic {
+        range: Range::new(
+            position_at(contents, message.start),
+            position_at(contents, end),
+        ),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(message.identifier.clone())),
+        source: Some("statan".to_string()),
+        message: message.message.clone(),
+        ..Default::default()
+    }
+}
+
+/// The path of a document, relative to the working directory like the paths of the
+/// project's files so that its definitions can be replaced.
+fn document_path(uri: &Url) -> Option<PathBuf> {
+    let path = uri.to_file_path().ok()?;
+
+    Some(match current_dir() {
+        Ok(current) => match path.strip_prefix(&current) {
+            Ok(relative) => normalise(relative),
+            Err(_) => path,
+        },
+        Err(_) => path,
+    })
+}
+
+/// Convert a byte offset into a line and UTF-16 column, as used by the protocol.
+fn position_at(contents: &str, offset: usize) -> Position {
+    let mut offset = offset.min(contents.len());
+    while !contents.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let before = &contents[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let character = before[line_start..].encode_utf16().count();
+
+    Position::new(line as u32, character as u32)
+}
+
+/// Convert a line and UTF-16 column into a byte offset.
+fn offset_at(contents: &str, position: Position) -> usize {
+    let mut offset = 0;
+
+    for (index, line) in contents.split('\n').enumerate() {
+        if index == position.line as usize {
+            let mut character = 0;
+
+            for (byte, c) in line.char_indices() {
+                if character >= position.character as usize {
+                    return offset + byte;
+                }
+
+                character += c.len_utf16();
+            }
+
+            return offset + line.len();
+        }
+
+        offset += line.len() + 1;
+    }
+
+    contents.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Position;
+
+    use super::{offset_at, position_at};
+
+    #[test]
+    fn it_counts_columns_in_utf16_code_units() {
+        let contents = "<?php\n$café = '😀'; foo();\n";
+        let call = contents.find("foo").unwrap();
+
+        assert_eq!(position_at(contents, call), Position::new(1, 14));
+        assert_eq!(offset_at(contents, Position::new(1, 14)), call);
+    }
+
+    #[test]
+    fn it_handles_offsets_inside_of_a_character() {
+        let contents = "<?php\n$café = 1;\n";
+        let inside = contents.find('é').unwrap() + 1;
+
+        assert_eq!(position_at(contents, inside), Position::new(1, 4));
+    }
+}
@@ -1 +1,3 @@
 pub mod analyse;
+pub mod lsp;
+pub mod watch;
@@ -0,0 +1,254 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    env::current_dir,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{mpsc::channel, Arc},
+    time::Duration,
+};
+
+use colored::*;
+use notify::{recommended_watcher, EventKind, RecursiveMode, Watcher};
+use statan::{
+    analyser::messages::{Message, MessageCollector, MessageSeverity},
+    baseline::Baseline,
+    definitions::collection::DefinitionCollection,
+    project::{normalise, Project},
+    runner::Runner,
+};
+
+use super::analyse::EXIT_FAILURE;
+
+/// Changes that happen within this long of each other are handled together, since
+/// editors tend to write a file in several steps.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// The project as it was analysed before watching for changes.
+pub(crate) struct WatchState<'a, F> {
+    pub project: Project,
+    pub definitions: Arc<DefinitionCollection>,
+    pub runner: &'a Runner,
+    pub baseline: Option<&'a Baseline>,
+    /// Whether a file is analysed, rather than only used for its definitions.
+    pub is_analysed: F,
+    /// The messages that came out of the analysis, which are kept so that the
+    /// project rules can be run again.
+    pub analysed: Vec<MessageCollector>,
+    /// The messages that were printed after running the project rules and filtering.
+    pub results: Vec<MessageCollector>,
+    /// The project rules need the data from every file, so they're only run when
+    /// every file was analysed.
+    pub run_project_rules: bool,
+}
+
+/// Keep the project in memory and analyse it again whenever a file changes, printing
+/// the messages that were added or fixed by the change. This only returns if the
+/// project can't be watched.
+pub(crate) fn run(state: WatchState<impl Fn(&Path) -> bool + Sync + Send>) -> ExitCode {
+    let WatchState {
+        mut project,
+        mut definitions,
+        runner,
+        baseline,
+        is_analysed,
+        analysed,
+        results,
+        run_project_rules,
+    } = state;
+    let configuration = runner.configuration();
+    let by_file = |message_collections: Vec<MessageCollector>| {
+        message_collections
+            .into_iter()
+            .map(|messages| (messages.get_file().to_string(), messages))
+            .collect::<BTreeMap<String, MessageCollector>>()
+    };
+
+    let mut analysed = by_file(analysed);
+    let mut results = by_file(results);
+
+    let (sender, receiver) = channel();
+    let mut watcher = match recommended_watcher(sender) {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            eprintln!("{}", format!("failed to watch the project: {error}").red());
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    if let Err(error) = watcher.watch(configuration.root(), RecursiveMode::Recursive) {
+        eprintln!("{}", format!("failed to watch the project: {error}").red());
+        return ExitCode::from(EXIT_FAILURE);
+    }
+
+    eprintln!("{}", "> Watching for changes...".yellow());
+
+    let current = current_dir().unwrap_or_default();
+
+    while let Ok(event) = receiver.recv() {
+        let mut events = vec![event];
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE) {
+            events.push(event);
+        }
+
+        // Event paths are absolute, but the project's paths are relative to the
+        // working directory.
+        let changed = events
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|event| !matches!(event.kind, EventKind::Access(_)))
+            .flat_map(|event| event.paths)
+            .map(|path| normalise(path.strip_prefix(&current).unwrap_or(&path)))
+            .filter(|path| configuration.has_extension(path))
+            .filter(|path| !configuration.is_excluded(path))
+            .filter(|path| project.get_file(path).is_some() || is_analysed(path))
+            .collect::<BTreeSet<PathBuf>>();
+
+        if changed.is_empty() {
+            continue;
+        }
+
+        // Collect the definitions from the changed files again, keeping track of the
+        // classes and functions they defined before and after the change.
+        let mut changed_classes = HashSet::new();
+        let mut changed_functions = HashSet::new();
+        let collection = Arc::make_mut(&mut definitions);
+
+        for path in changed.iter() {
+            changed_classes.extend(collection.classish_names_in_file(path));
+            changed_functions.extend(collection.function_names_in_file(path));
+            collection.remove_file(path);
+
+            if let Some(file_definitions) = project.reload_file(path) {
+                changed_classes.extend(file_definitions.classish_names_in_file(path));
+                changed_functions.extend(file_definitions.function_names_in_file(path));
+                collection.merge(file_definitions);
+            }
+        }
+
+        project.retain_asts(&is_analysed);
+
+        // Files that use one of the changed classes or functions might have new or
+        // fixed errors too. That includes subclasses and implementations of a changed
+        // class, and files that use it as a type.
+        let affected = project
+            .files()
+            .iter()
+            .filter(|file| {
+                changed.contains(file.path())
+                    || file
+                        .references()
+                        .iter()
+                        .any(|name| changed_classes.contains(name))
+                    || file
+                        .function_references()
+                        .iter()
+                        .any(|name| changed_functions.contains(name))
+            })
+            .map(|file| file.path().to_path_buf())
+            .collect::<HashSet<PathBuf>>();
+
+        let message_collections = project.analyse(
+            |path| affected.contains(path) && is_analysed(path),
+            || runner.create_analyser(definitions.clone()),
+            |_| {},
+        );
+
+        // Files that were deleted don't have any messages anymore.
+        for path in changed.iter() {
+            if project.get_file(path).is_none() {
+                analysed.remove(path.to_string_lossy().as_ref());
+            }
+        }
+
+        analysed.extend(by_file(message_collections));
+
+        // The project rules can report messages in any file, so they're run on the
+        // whole project and every file is compared to its previous results.
+        let mut latest = analysed
+            .values()
+            .cloned()
+            .collect::<Vec<MessageCollector>>();
+        if run_project_rules {
+            runner.run_project_rules(&definitions, &mut latest);
+        }
+        runner.filter_messages(baseline, &mut latest);
+        let latest = by_file(latest);
+
+        for (file, previous) in results.iter() {
+            if !latest.contains_key(file) {
+                print_diff(file, previous.iter().collect(), Vec::new());
+            }
+        }
+
+        for (file, messages) in latest.iter() {
+            print_diff(
+                file,
+                results
+                    .get(file)
+                    .map_or(Vec::new(), |previous| previous.iter().collect()),
+                messages.iter().collect(),
+            );
+        }
+
+        results = latest;
+
+        let errors = results
+            .values()
+            .flat_map(|messages| messages.iter())
+            .filter(|message| message.severity == MessageSeverity::Error)
+            .count();
+
+        eprintln!(
+            "{}",
+            format!("> {errors} error(s) in the project, watching for changes...").yellow()
+        );
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Print the messages that were added to or removed from a file. Messages are
+/// compared by their identifier and text, since their lines move around as the
+/// file is edited.
+fn print_diff(file: &str, mut previous: Vec<&Message>, current: Vec<&Message>) {
+    let mut added = Vec::new();
+
+    for message in current {
+        match previous.iter().position(|other| {
+            other.identifier == message.identifier && other.message == message.message
+        }) {
+            Some(index) => {
+                previous.remove(index);
+            }
+            None => added.push(message),
+        }
+    }
+
+    if added.is_empty() && previous.is_empty() {
+        return;
+    }
+
+    println!("{}", file.bold());
+
+    for message in added {
+        println!(
+            "{}",
+            format!(
+                "  + {}:{} {} ({})",
+                message.line, message.column, message.message, message.identifier
+            )
+            .red()
+        );
+    }
+
+    for message in previous {
+        println!(
+            "{}",
+            format!(
+                "  - {}:{} {} ({})",
+                message.line, message.column, message.message, message.identifier
+            )
+            .green()
+        );
+    }
+}
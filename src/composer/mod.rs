@@ -0,0 +1,250 @@
+use std::{
+    collections::BTreeMap,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use pxp_parser::lexer::byte_string::ByteString;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+struct ComposerJson {
+    #[serde(default)]
+    autoload: AutoloadJson,
+    #[serde(default, rename = "autoload-dev")]
+    autoload_dev: AutoloadJson,
+    #[serde(default)]
+    config: ConfigJson,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ConfigJson {
+    #[serde(rename = "vendor-dir")]
+    vendor_dir: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AutoloadJson {
+    #[serde(default, rename = "psr-4")]
+    psr4: BTreeMap<String, PathsJson>,
+    #[serde(default, rename = "psr-0")]
+    psr0: BTreeMap<String, PathsJson>,
+    #[serde(default)]
+    classmap: Vec<String>,
+    #[serde(default)]
+    files: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PathsJson {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl PathsJson {
+    fn paths(&self) -> Vec<&str> {
+        match self {
+            PathsJson::Single(path) => vec![path.as_str()],
+            PathsJson::Multiple(paths) => paths.iter().map(|path| path.as_str()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum InstalledJson {
+    // Composer 2 wraps the list of packages in an object.
+    V2 { packages: Vec<InstalledPackageJson> },
+    V1(Vec<InstalledPackageJson>),
+}
+
+#[derive(Debug, Deserialize)]
+struct InstalledPackageJson {
+    name: String,
+    #[serde(default)]
+    autoload: AutoloadJson,
+    #[serde(rename = "install-path")]
+    install_path: Option<String>,
+}
+
+/// The autoload mappings for a set of packages, with every path resolved
+/// relative to the package that declared it.
+#[derive(Debug, Default, Clone)]
+pub struct Autoloader {
+    psr4: Vec<(String, PathBuf)>,
+    psr0: Vec<(String, PathBuf)>,
+    classmap: Vec<PathBuf>,
+    files: Vec<PathBuf>,
+}
+
+impl Autoloader {
+    fn extend(&mut self, root: &Path, autoload: &AutoloadJson) {
+        for (prefix, paths) in autoload.psr4.iter() {
+            for path in paths.paths() {
+                self.psr4.push((prefix.clone(), root.join(path)));
+            }
+        }
+
+        for (prefix, paths) in autoload.psr0.iter() {
+            for path in paths.paths() {
+                self.psr0.push((prefix.clone(), root.join(path)));
+            }
+        }
+
+        self.classmap
+            .extend(autoload.classmap.iter().map(|path| root.join(path)));
+        self.files
+            .extend(autoload.files.iter().map(|path| root.join(path)));
+    }
+
+    fn sort(&mut self) {
+        // Longer prefixes are more specific, so they should be checked first.
+        self.psr4.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+        self.psr0.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+    }
+
+    /// Find the file that should contain the given fully qualified class name,
+    /// using the PSR-4 mappings first and then falling back to PSR-0.
+    pub fn find_file(&self, class: &ByteString) -> Option<PathBuf> {
+        let class = String::from_utf8_lossy(&class.bytes);
+        let class = class.trim_start_matches('\\');
+
+        for (prefix, directory) in self.psr4.iter() {
+            if let Some(relative) = class.strip_prefix(prefix.as_str()) {
+                let path = directory.join(format!("{}.php", relative.replace('\\', "/")));
+
+                if path.is_file() {
+                    return Some(path);
+                }
+            }
+        }
+
+        for (prefix, directory) in self.psr0.iter() {
+            if !class.starts_with(prefix.as_str()) {
+                continue;
+            }
+
+            // PSR-0 treats underscores in the class name (but not the namespace)
+            // as directory separators.
+            let (namespace, name) = match class.rfind('\\') {
+                Some(position) => (&class[..=position], &class[position + 1..]),
+                None => ("", class),
+            };
+
+            let path = directory.join(format!(
+                "{}{}.php",
+                namespace.replace('\\', "/"),
+                name.replace('_', "/")
+            ));
+
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+
+        None
+    }
+}
+
+/// A Composer project, read from `composer.json` and the list of installed
+/// packages in `vendor/composer/installed.json`.
+#[derive(Debug, Clone)]
+pub struct ComposerProject {
+    root: PathBuf,
+    project: Autoloader,
+    vendor: Autoloader,
+}
+
+impl ComposerProject {
+    /// Load the Composer project in the given directory. Returns `Ok(None)` when
+    /// the directory does not contain a `composer.json` file.
+    pub fn load(root: &Path) -> Result<Option<Self>, String> {
+        let composer_json_path = root.join("composer.json");
+
+        if !composer_json_path.is_file() {
+            return Ok(None);
+        }
+
+        let composer_json: ComposerJson = read_json(&composer_json_path)?;
+
+        let mut project = Autoloader::default();
+        project.extend(root, &composer_json.autoload);
+        project.extend(root, &composer_json.autoload_dev);
+        project.sort();
+
+        let vendor_dir = root.join(
+            composer_json
+                .config
+                .vendor_dir
+                .as_deref()
+                .unwrap_or("vendor"),
+        );
+        let installed_json_path = vendor_dir.join("composer").join("installed.json");
+
+        let mut vendor = Autoloader::default();
+
+        // Dependencies might not have been installed yet, in which case we can
+        // only rely on the project's own autoload configuration.
+        if installed_json_path.is_file() {
+            let packages = match read_json::<InstalledJson>(&installed_json_path)? {
+                InstalledJson::V2 { packages } => packages,
+                InstalledJson::V1(packages) => packages,
+            };
+
+            for package in packages.iter() {
+                let package_root = match &package.install_path {
+                    // The install path is relative to the `vendor/composer` directory.
+                    Some(install_path) => vendor_dir.join("composer").join(install_path),
+                    None => vendor_dir.join(&package.name),
+                };
+
+                vendor.extend(&package_root, &package.autoload);
+            }
+        }
+
+        vendor.sort();
+
+        Ok(Some(Self {
+            root: root.to_path_buf(),
+            project,
+            vendor,
+        }))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The paths that should be eagerly scanned for definitions. This includes all
+    /// of the project's own autoload paths, as well as the classmap and files
+    /// entries of installed packages since those cannot be resolved lazily.
+    /// PSR-4 and PSR-0 classes in installed packages are resolved on demand with
+    /// [`ComposerProject::find_class_file`].
+    pub fn definition_paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+
+        paths.extend(self.project.psr4.iter().map(|(_, path)| path.clone()));
+        paths.extend(self.project.psr0.iter().map(|(_, path)| path.clone()));
+        paths.extend(self.project.classmap.iter().cloned());
+        paths.extend(self.project.files.iter().cloned());
+        paths.extend(self.vendor.classmap.iter().cloned());
+        paths.extend(self.vendor.files.iter().cloned());
+
+        paths.into_iter().filter(|path| path.exists()).collect()
+    }
+
+    pub fn find_class_file(&self, class: &ByteString) -> Option<PathBuf> {
+        self.project
+            .find_file(class)
+            .or_else(|| self.vendor.find_file(class))
+    }
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let contents = read_to_string(path)
+        .map_err(|error| format!("failed to read {}: {error}", path.display()))?;
+
+    serde_json::from_str(&contents)
+        .map_err(|error| format!("failed to parse {}: {error}", path.display()))
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::BTreeMap,
+    env::current_dir,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{
+    analyser::messages::{Message, MessageSeverity},
+    baseline::BASELINE_FILE,
+    project::normalise,
+    rules::MAX_LEVEL,
+    stubs::PhpVersion,
+};
+
+pub const CONFIGURATION_FILE: &str = "statan.toml";
+
+/// The extensions of the files that are scanned and analysed by default.
+pub const DEFAULT_EXTENSIONS: [&str; 2] = ["php", "pxp"];
+
+/// Files that mark the root of a project that doesn't have a configuration file.
+const PROJECT_FILES: [&str; 3] = [CONFIGURATION_FILE, "composer.json", ".git"];
+
+/// The project configuration, read from a `statan.toml` file.
+///
+/// ```toml
+/// paths = ["src"]
+/// scan-paths = ["bootstrap"]
+/// excludes = ["src/Legacy"]
+/// stubs = ["stubs/extensions.php"]
+/// extensions = ["php", "pxp", "inc"]
+/// php-version = "8.1"
+/// level = 5
+/// report-unmatched-ignores = false
+/// baseline = "statan-baseline.toml"
+///
+/// [rules]
+/// disabled = ["FunctionDefinitionRule"]
+///
+/// [rules.severity]
+/// ValidAssignmentRule = "warning"
+///
+/// [rules.dead-code]
+/// report-unused-public = true
+/// entry-points = ["App\\Http\\Controllers\\*", "*::handle"]
+///
+/// [[ignore]]
+/// message = "Function `.*` not found"
+/// path = "src/helpers.php"
+///
+/// [[ignore]]
+/// identifier = "missingType.return"
+/// path = "src/Legacy"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Configuration {
+    /// The paths that should be analysed.
+    pub paths: Vec<PathBuf>,
+    /// Additional paths that should be scanned for definitions, but not analysed.
+    pub scan_paths: Vec<PathBuf>,
+    /// Paths that should neither be scanned nor analysed.
+    pub excludes: Vec<PathBuf>,
+    /// Stub files that should be scanned for definitions.
+    pub stubs: Vec<PathBuf>,
+    /// The extensions of the files to scan and analyse. Defaults to `php` and `pxp`.
+    pub extensions: Option<Vec<String>>,
+    pub php_version: Option<PhpVersion>,
+    /// The strictness level, from 0 to 9. Defaults to the strictest level.
+    pub level: Option<u8>,
+    pub rules: RulesConfiguration,
+    pub ignore: Vec<IgnorePattern>,
+    /// Whether `@statan-ignore` comments that don't match any message should be
+    /// reported. Defaults to `true`.
+    pub report_unmatched_ignores: Option<bool>,
+    /// The baseline file. Defaults to `statan-baseline.toml` next to the configuration file.
+    pub baseline: Option<PathBuf>,
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RulesConfiguration {
+    /// If set, only the rules in this list will be run, regardless of their level.
+    pub enabled: Option<Vec<String>>,
+    pub disabled: Vec<String>,
+    /// Overrides for the severity of the messages reported by a rule.
+    pub severity: BTreeMap<String, MessageSeverity>,
+    pub dead_code: DeadCodeConfiguration,
+}
+
+/// Options for `DeadCodeRule` and `UnusedPublicCodeRule`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DeadCodeConfiguration {
+    /// Whether public functions, classes and methods that are never used by the
+    /// analysed paths should be reported. Defaults to `false`.
+    pub report_unused_public: bool,
+    /// Functions, classes and methods that are used from outside of the analysed
+    /// paths, such as controllers. `*` matches any part of a name, e.g.
+    /// `App\Http\Controllers\*` or `*::handle`.
+    pub entry_points: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "RawIgnorePattern")]
+pub struct IgnorePattern {
+    message: Option<Regex>,
+    path: Option<PathBuf>,
+    identifier: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct RawIgnorePattern {
+    message: Option<String>,
+    path: Option<PathBuf>,
+    identifier: Option<String>,
+}
+
+impl TryFrom<RawIgnorePattern> for IgnorePattern {
+    type Error = String;
+
+    fn try_from(value: RawIgnorePattern) -> Result<Self, Self::Error> {
+        if value.message.is_none() && value.path.is_none() && value.identifier.is_none() {
+            return Err("ignore patterns require a message, a path or an identifier".to_string());
+        }
+
+        let message = value
+            .message
+            .map(|message| Regex::new(&message))
+            .transpose()
+            .map_err(|error| error.to_string())?;
+
+        Ok(Self {
+            message,
+            path: value.path,
+            identifier: value.identifier,
+        })
+    }
+}
+
+impl IgnorePattern {
+    pub fn matches(&self, file: &Path, message: &Message) -> bool {
+        if let Some(pattern) = &self.message {
+            if !pattern.is_match(&message.message) {
+                return false;
+            }
+        }
+
+        if let Some(path) = &self.path {
+            if !file.starts_with(path) {
+                return false;
+            }
+        }
+
+        if let Some(identifier) = &self.identifier {
+            if *identifier != message.identifier {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl Configuration {
+    /// Find the nearest configuration file, starting in the given directory and
+    /// working upwards through its parents.
+    pub fn discover(directory: &Path) -> Option<PathBuf> {
+        directory
+            .ancestors()
+            .map(|directory| directory.join(CONFIGURATION_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// The default configuration for the project that contains the given directory,
+    /// which is the nearest directory with a `composer.json` file or a git repository.
+    pub fn for_directory(directory: &Path) -> Self {
+        let root = directory
+            .ancestors()
+            .find(|directory| {
+                PROJECT_FILES
+                    .iter()
+                    .any(|file| directory.join(file).exists())
+            })
+            .unwrap_or(directory);
+
+        let mut configuration = Self::default();
+        configuration.resolve_paths(&relative_to_current(root));
+        configuration
+    }
+
+    /// Load the configuration file at the given path. Paths inside of the file
+    /// are resolved relative to the directory that contains it.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = read_to_string(path)
+            .map_err(|error| format!("failed to read {}: {error}", path.display()))?;
+
+        let mut configuration: Configuration = toml::from_str(&contents)
+            .map_err(|error| format!("failed to parse {}: {error}", path.display()))?;
+
+        let root = path.parent().unwrap_or(Path::new("."));
+        configuration.resolve_paths(&relative_to_current(root));
+
+        Ok(configuration)
+    }
+
+    fn resolve_paths(&mut self, root: &Path) {
+        let resolve = |paths: &mut Vec<PathBuf>| {
+            for path in paths.iter_mut() {
+                *path = normalise(&root.join(path.as_path()));
+            }
+        };
+
+        resolve(&mut self.paths);
+        resolve(&mut self.scan_paths);
+        resolve(&mut self.excludes);
+        resolve(&mut self.stubs);
+
+        if let Some(baseline) = &self.baseline {
+            self.baseline = Some(normalise(&root.join(baseline)));
+        }
+
+        for pattern in self.ignore.iter_mut() {
+            if let Some(path) = &pattern.path {
+                pattern.path = Some(normalise(&root.join(path)));
+            }
+        }
+
+        self.root = root.to_path_buf();
+    }
+
+    /// The directory that contains the configuration file, or the current directory
+    /// if no configuration file was loaded.
+    pub fn root(&self) -> &Path {
+        if self.root.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            &self.root
+        }
+    }
+
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        self.excludes
+            .iter()
+            .any(|exclude| path.starts_with(exclude))
+    }
+
+    pub fn is_ignored(&self, file: &Path, message: &Message) -> bool {
+        self.ignore
+            .iter()
+            .any(|pattern| pattern.matches(file, message))
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level.unwrap_or(MAX_LEVEL)
+    }
+
+    /// The path of the baseline file, whether or not it exists.
+    pub fn baseline(&self) -> PathBuf {
+        match &self.baseline {
+            Some(baseline) => baseline.clone(),
+            None => self.root().join(BASELINE_FILE),
+        }
+    }
+
+    pub fn extensions(&self) -> Vec<String> {
+        match &self.extensions {
+            Some(extensions) => extensions.clone(),
+            None => DEFAULT_EXTENSIONS.map(String::from).to_vec(),
+        }
+    }
+
+    /// Whether the file has one of the extensions that are scanned and analysed.
+    pub fn has_extension(&self, path: &Path) -> bool {
+        path.extension().is_some_and(|extension| {
+            self.extensions()
+                .iter()
+                .any(|other| extension == other.as_str())
+        })
+    }
+
+    pub fn report_unmatched_ignores(&self) -> bool {
+        self.report_unmatched_ignores.unwrap_or(true)
+    }
+
+    /// Check that the level is in range and that every rule the configuration refers
+    /// to is one of the given rules, which are given by name and level.
+    pub fn validate_rules(&self, rules: &[(&str, u8)]) -> Result<(), String> {
+        if self.level() > MAX_LEVEL {
+            return Err(format!(
+                "invalid level {}, expected a level between 0 and {MAX_LEVEL}",
+                self.level()
+            ));
+        }
+
+        let configured = self
+            .rules
+            .enabled
+            .iter()
+            .flatten()
+            .chain(self.rules.disabled.iter())
+            .chain(self.rules.severity.keys());
+
+        for name in configured {
+            if !rules.iter().any(|(rule, _)| rule == name) {
+                return Err(format!("unknown rule {name} in configuration"));
+            }
+        }
+
+        let is_enabled = |name: &str| {
+            rules
+                .iter()
+                .any(|(rule, level)| *rule == name && self.is_rule_enabled(rule, *level))
+        };
+
+        // `UnusedPublicCodeRule` reports code that isn't in the names collected by
+        // `DeadCodeRule`, so it can't do anything without it.
+        if self.rules.dead_code.report_unused_public
+            && is_enabled("UnusedPublicCodeRule")
+            && !is_enabled("DeadCodeRule")
+        {
+            return Err(
+                "UnusedPublicCodeRule needs DeadCodeRule to be enabled to report unused public code"
+                    .to_string(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Whether the rule with the given name and level should be run, based on the
+    /// level and the enabled and disabled rule lists.
+    pub fn is_rule_enabled(&self, name: &str, level: u8) -> bool {
+        let enabled = match &self.rules.enabled {
+            Some(enabled) => enabled.iter().any(|enabled| enabled == name),
+            None => level <= self.level(),
+        };
+
+        enabled && !self.rules.disabled.iter().any(|disabled| disabled == name)
+    }
+}
+
+/// Paths are kept relative to the working directory when possible so that they're
+/// easier to read in the output.
+fn relative_to_current(path: &Path) -> PathBuf {
+    match (path.canonicalize(), current_dir()) {
+        (Ok(path), Ok(current)) => match path.strip_prefix(&current) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => path,
+        },
+        _ => path.to_path_buf(),
+    }
+}
@@ -4,12 +4,13 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     collection::DefinitionCollection, constants::ConstantDefinition, functions::MethodDefinition,
-    property::PropertyDefinition,
+    location::DefinitionLocation, property::PropertyDefinition,
 };
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ClassDefinition {
     pub name: ByteString,
+    pub location: DefinitionLocation,
     pub modifiers: Vec<Modifier>,
     pub extends: Option<ByteString>,
     pub implements: Vec<ByteString>,
@@ -34,24 +35,27 @@ impl ClassDefinition {
         definitions: &'a DefinitionCollection,
         context: &Context,
     ) -> Option<&'a MethodDefinition> {
-        self.methods.iter().find(|m| m.name == *name).or_else(|| {
-            for trait_ in &self.uses {
-                let trait_ = definitions.get_trait(trait_, context);
-
-                if trait_.is_none() {
-                    continue;
-                }
-
-                let trait_ = trait_.unwrap();
-                let method = trait_.get_method(name, definitions, context);
-
-                if method.is_some() {
-                    return method;
+        self.methods
+            .iter()
+            .find(|m| m.name.eq_ignore_ascii_case(name))
+            .or_else(|| {
+                for trait_ in &self.uses {
+                    let trait_ = definitions.get_trait(trait_, context);
+
+                    if trait_.is_none() {
+                        continue;
+                    }
+
+                    let trait_ = trait_.unwrap();
+                    let method = trait_.get_method(name, definitions, context);
+
+                    if method.is_some() {
+                        return method;
+                    }
                 }
-            }
 
-            None
-        })
+                None
+            })
     }
 
     pub fn get_inherited_method<'a>(
@@ -1,11 +1,13 @@
+use std::{collections::HashSet, path::Path};
+
 use pxp_parser::lexer::byte_string::ByteString;
 use serde::{Deserialize, Serialize};
 
-use crate::analyser::context::Context;
+use crate::{analyser::context::Context, shared::types::Type};
 
 use super::{
     classes::ClassDefinition, enums::EnumDefinition, functions::FunctionDefinition,
-    interfaces::InterfaceDefinition, traits::TraitDefinition,
+    interfaces::InterfaceDefinition, location::DefinitionLocation, traits::TraitDefinition,
 };
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -48,6 +50,163 @@ impl DefinitionCollection {
         self.enums.push(enum_);
     }
 
+    pub fn merge(&mut self, other: DefinitionCollection) {
+        self.functions.extend(other.functions);
+        self.classes.extend(other.classes);
+        self.interfaces.extend(other.interfaces);
+        self.traits.extend(other.traits);
+        self.enums.extend(other.enums);
+    }
+
+    /// Remove every definition that was collected from the given file, so that the
+    /// file can be collected again after it has changed.
+    pub fn remove_file(&mut self, file: &Path) {
+        let is_other_file = |location: &DefinitionLocation| location.file.as_deref() != Some(file);
+
+        self.functions
+            .retain(|function| is_other_file(&function.location));
+        self.classes.retain(|class| is_other_file(&class.location));
+        self.interfaces
+            .retain(|interface| is_other_file(&interface.location));
+        self.traits.retain(|trait_| is_other_file(&trait_.location));
+        self.enums.retain(|enum_| is_other_file(&enum_.location));
+    }
+
+    /// Get the fully qualified names of the classes, interfaces, traits and enums
+    /// that were collected from the given file.
+    pub fn classish_names_in_file(&self, file: &Path) -> Vec<ByteString> {
+        let in_file = |location: &DefinitionLocation| location.file.as_deref() == Some(file);
+
+        self.classes
+            .iter()
+            .filter(|class| in_file(&class.location))
+            .map(|class| class.name.clone())
+            .chain(
+                self.interfaces
+                    .iter()
+                    .filter(|interface| in_file(&interface.location))
+                    .map(|interface| interface.name.clone()),
+            )
+            .chain(
+                self.traits
+                    .iter()
+                    .filter(|trait_| in_file(&trait_.location))
+                    .map(|trait_| trait_.name.clone()),
+            )
+            .chain(
+                self.enums
+                    .iter()
+                    .filter(|enum_| in_file(&enum_.location))
+                    .map(|enum_| enum_.name.clone()),
+            )
+            .collect()
+    }
+
+    /// Get the fully qualified names of the functions that were collected from the
+    /// given file.
+    pub fn function_names_in_file(&self, file: &Path) -> Vec<ByteString> {
+        self.functions
+            .iter()
+            .filter(|function| function.location.file.as_deref() == Some(file))
+            .map(|function| function.name.clone())
+            .collect()
+    }
+
+    pub fn functions(&self) -> &[FunctionDefinition] {
+        &self.functions
+    }
+
+    pub fn classes(&self) -> &[ClassDefinition] {
+        &self.classes
+    }
+
+    pub fn interfaces(&self) -> &[InterfaceDefinition] {
+        &self.interfaces
+    }
+
+    pub fn traits(&self) -> &[TraitDefinition] {
+        &self.traits
+    }
+
+    pub fn enums(&self) -> &[EnumDefinition] {
+        &self.enums
+    }
+
+    /// Check if a class, interface, trait or enum with the given fully qualified
+    /// name has been defined.
+    pub fn has_classish(&self, name: &ByteString) -> bool {
+        self.classes.iter().any(|class| class.name == *name)
+            || self
+                .interfaces
+                .iter()
+                .any(|interface| interface.name == *name)
+            || self.traits.iter().any(|trait_| trait_.name == *name)
+            || self.enums.iter().any(|enum_| enum_.name == *name)
+    }
+
+    /// Get the fully qualified names of every class, interface, trait or enum that
+    /// is referenced by a definition in this collection, e.g. as a parent class or
+    /// as the type of a parameter.
+    pub fn referenced_classes(&self) -> Vec<ByteString> {
+        let mut names: Vec<&ByteString> = Vec::new();
+        let mut types: Vec<&Type> = Vec::new();
+
+        for function in self.functions.iter() {
+            types.extend(function.parameters.iter().filter_map(|p| p.type_.as_ref()));
+            types.extend(function.return_type.iter());
+        }
+
+        for class in self.classes.iter() {
+            names.extend(class.extends.iter());
+            names.extend(class.implements.iter());
+            names.extend(class.uses.iter());
+            types.extend(class.properties.iter().filter_map(|p| p.type_.as_ref()));
+
+            for method in class.methods.iter() {
+                types.extend(method.parameters.iter().filter_map(|p| p.type_.as_ref()));
+                types.extend(method.return_type.iter());
+            }
+        }
+
+        for interface in self.interfaces.iter() {
+            names.extend(interface.extends.iter());
+
+            for method in interface.methods.iter() {
+                types.extend(method.parameters.iter().filter_map(|p| p.type_.as_ref()));
+                types.extend(method.return_type.iter());
+            }
+        }
+
+        for trait_ in self.traits.iter() {
+            names.extend(trait_.uses.iter());
+            types.extend(trait_.properties.iter().filter_map(|p| p.type_.as_ref()));
+
+            for method in trait_.methods.iter() {
+                types.extend(method.parameters.iter().filter_map(|p| p.type_.as_ref()));
+                types.extend(method.return_type.iter());
+            }
+        }
+
+        for enum_ in self.enums.iter() {
+            names.extend(enum_.implements.iter());
+
+            for method in enum_.methods.iter() {
+                types.extend(method.parameters.iter().filter_map(|p| p.type_.as_ref()));
+                types.extend(method.return_type.iter());
+            }
+        }
+
+        names.extend(types.into_iter().flat_map(|ty| ty.referenced_names()));
+
+        let mut seen = HashSet::new();
+
+        names
+            .into_iter()
+            .filter(|name| seen.insert(*name))
+            .cloned()
+            .collect()
+    }
+
     pub fn get_function(
         &self,
         name: &ByteString,
@@ -84,6 +243,27 @@ impl DefinitionCollection {
             })
     }
 
+    pub fn get_interface(
+        &self,
+        name: &ByteString,
+        context: &Context,
+    ) -> Option<&InterfaceDefinition> {
+        let resolved_name = context.resolve_name(name);
+
+        self.interfaces
+            .iter()
+            .find(|interface| interface.name == resolved_name)
+            .or_else(|| {
+                let mut global_name = ByteString::default();
+                global_name.extend(b"\\");
+                global_name.extend(&name.bytes);
+
+                self.interfaces
+                    .iter()
+                    .find(|interface| interface.name == global_name)
+            })
+    }
+
     pub fn get_trait(&self, name: &ByteString, context: &Context) -> Option<&TraitDefinition> {
         let resolved_name = context.resolve_name(name);
 
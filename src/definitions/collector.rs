@@ -1,18 +1,21 @@
+use std::path::PathBuf;
+
 use pxp_parser::{
     downcast::downcast,
-    lexer::byte_string::ByteString,
+    lexer::{byte_string::ByteString, token::Span},
     node::Node,
     parser::ast::{
         classes::{ClassMember, ClassStatement},
         data_type::Type as ParsedType,
         enums::{BackedEnumMember, BackedEnumStatement, UnitEnumMember, UnitEnumStatement},
         functions::{FunctionStatement, ReturnType},
-        identifiers::SimpleIdentifier,
+        identifiers::{Identifier, SimpleIdentifier},
         interfaces::{InterfaceMember, InterfaceStatement},
         modifiers::MethodModifier,
         namespaces::{BracedNamespace, UnbracedNamespace},
         traits::{TraitMember, TraitStatement},
-        GroupUseStatement, Statement, Use, UseStatement,
+        Expression, FunctionCallExpression, GroupUseStatement, NewExpression, Statement,
+        StaticMethodCallExpression, Use, UseStatement,
     },
     traverser::Visitor,
 };
@@ -26,6 +29,7 @@ use super::{
     enums::EnumDefinition,
     functions::{FunctionDefinition, MethodDefinition},
     interfaces::InterfaceDefinition,
+    location::DefinitionLocation,
     parameter::Parameter,
     property::PropertyDefinition,
     traits::TraitDefinition,
@@ -36,6 +40,9 @@ pub struct DefinitionCollector {
     current_namespace: ByteString,
     imported_names: Vec<ByteString>,
     collection: DefinitionCollection,
+    references: Vec<ByteString>,
+    function_references: Vec<ByteString>,
+    file: Option<PathBuf>,
 }
 
 impl DefinitionCollector {
@@ -44,6 +51,27 @@ impl DefinitionCollector {
             current_namespace: ByteString::default(),
             imported_names: Vec::new(),
             collection: DefinitionCollection::default(),
+            references: Vec::new(),
+            function_references: Vec::new(),
+            file: None,
+        }
+    }
+
+    /// Create a collector for the given file, so that the locations of the collected
+    /// definitions point to it.
+    pub fn for_file(file: PathBuf) -> Self {
+        Self {
+            file: Some(file),
+            ..Self::new()
+        }
+    }
+
+    fn location(&self, span: Span) -> DefinitionLocation {
+        DefinitionLocation {
+            file: self.file.clone(),
+            line: span.line,
+            column: span.column,
+            offset: span.position,
         }
     }
 
@@ -134,6 +162,20 @@ impl DefinitionCollector {
         self.collection.clone()
     }
 
+    /// Get the fully qualified names of the classes that are instantiated or
+    /// called statically by the scanned code, or that are referenced by its
+    /// definitions, e.g. as a parent class or as the type of a parameter.
+    pub fn references(&self) -> &[ByteString] {
+        &self.references
+    }
+
+    /// Get the fully qualified names of the functions that are called by the
+    /// scanned code. Unqualified calls inside of a namespace fall back to the
+    /// global function, so both names are included for them.
+    pub fn function_references(&self) -> &[ByteString] {
+        &self.function_references
+    }
+
     pub fn scan(&mut self, ast: &mut [Statement]) {
         self.current_namespace = ByteString::default();
         self.imported_names = Vec::new();
@@ -141,6 +183,8 @@ impl DefinitionCollector {
         for statement in ast.iter_mut() {
             self.visit_node(statement).unwrap();
         }
+
+        self.references.extend(self.collection.referenced_classes());
     }
 }
 
@@ -184,6 +228,46 @@ impl Visitor<()> for DefinitionCollector {
             }
         }
 
+        if let Some(NewExpression { target, .. }) = downcast::<NewExpression>(node) {
+            if let Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                value,
+                ..
+            })) = target.as_ref()
+            {
+                self.references.push(self.resolve_name(value));
+            }
+        }
+
+        if let Some(FunctionCallExpression { target, .. }) =
+            downcast::<FunctionCallExpression>(node)
+        {
+            if let Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                value,
+                ..
+            })) = target.as_ref()
+            {
+                self.function_references.push(self.resolve_name(value));
+
+                if !value.contains(&b'\\') {
+                    let mut global_name = ByteString::from(b"\\");
+                    global_name.extend(&value.bytes);
+                    self.function_references.push(global_name);
+                }
+            }
+        }
+
+        if let Some(StaticMethodCallExpression { target, .. }) =
+            downcast::<StaticMethodCallExpression>(node)
+        {
+            if let Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                value,
+                ..
+            })) = target.as_ref()
+            {
+                self.references.push(self.resolve_name(value));
+            }
+        }
+
         if let Some(FunctionStatement {
             name,
             parameters,
@@ -191,6 +275,7 @@ impl Visitor<()> for DefinitionCollector {
             ..
         }) = downcast::<FunctionStatement>(node)
         {
+            let location = self.location(name.span);
             let name = self.qualify_name(&name.value);
             let parameters = parameters
                 .parameters
@@ -211,6 +296,7 @@ impl Visitor<()> for DefinitionCollector {
 
             self.collection.add_function(FunctionDefinition {
                 name,
+                location,
                 parameters,
                 return_type,
             })
@@ -230,6 +316,7 @@ impl Visitor<()> for DefinitionCollector {
                 .iter()
                 .map(|m| m.clone().into())
                 .collect::<Vec<Modifier>>();
+            let location = self.location(name.span);
             let name = self.qualify_name(&name.value);
 
             let extends = extends
@@ -341,6 +428,7 @@ impl Visitor<()> for DefinitionCollector {
                 })
                 .map(|m| MethodDefinition {
                     name: m.name.value.clone(),
+                    location: self.location(m.name.span),
                     visibility: m.modifiers.visibility().into(),
                     modifiers: m
                         .modifiers
@@ -385,6 +473,7 @@ impl Visitor<()> for DefinitionCollector {
                     })
                     .map(|m| MethodDefinition {
                         name: m.name.value.clone(),
+                        location: self.location(m.name.span),
                         visibility: m.modifiers.visibility().into(),
                         modifiers: m
                             .modifiers
@@ -423,6 +512,7 @@ impl Visitor<()> for DefinitionCollector {
 
             self.collection.add_class(ClassDefinition {
                 name,
+                location,
                 modifiers,
                 extends,
                 implements,
@@ -440,6 +530,7 @@ impl Visitor<()> for DefinitionCollector {
             ..
         }) = downcast::<InterfaceStatement>(node)
         {
+            let location = self.location(name.span);
             let name = self.qualify_name(&name.value);
             let extends = if let Some(extends) = extends {
                 extends
@@ -478,6 +569,7 @@ impl Visitor<()> for DefinitionCollector {
                 })
                 .map(|m| MethodDefinition {
                     name: m.name.value.clone(),
+                    location: self.location(m.name.span),
                     visibility: m.modifiers.visibility().into(),
                     modifiers: m
                         .modifiers
@@ -515,6 +607,7 @@ impl Visitor<()> for DefinitionCollector {
 
             self.collection.add_interface(InterfaceDefinition {
                 name,
+                location,
                 extends,
                 constants,
                 methods,
@@ -522,6 +615,7 @@ impl Visitor<()> for DefinitionCollector {
         }
 
         if let Some(TraitStatement { name, body, .. }) = downcast::<TraitStatement>(node) {
+            let location = self.location(name.span);
             let name = self.qualify_name(&name.value);
 
             let uses = body
@@ -618,6 +712,7 @@ impl Visitor<()> for DefinitionCollector {
                 })
                 .map(|m| MethodDefinition {
                     name: m.name.value.clone(),
+                    location: self.location(m.name.span),
                     visibility: m.modifiers.visibility().into(),
                     modifiers: m
                         .modifiers
@@ -662,6 +757,7 @@ impl Visitor<()> for DefinitionCollector {
                     })
                     .map(|m| MethodDefinition {
                         name: m.name.value.clone(),
+                        location: self.location(m.name.span),
                         visibility: m.modifiers.visibility().into(),
                         modifiers: m
                             .modifiers
@@ -700,6 +796,7 @@ impl Visitor<()> for DefinitionCollector {
 
             self.collection.add_trait(TraitDefinition {
                 name,
+                location,
                 uses,
                 constants,
                 properties,
@@ -714,6 +811,7 @@ impl Visitor<()> for DefinitionCollector {
             ..
         }) = downcast::<UnitEnumStatement>(node)
         {
+            let location = self.location(name.span);
             let name = self.qualify_name(&name.value);
             let implements = implements
                 .iter()
@@ -748,6 +846,7 @@ impl Visitor<()> for DefinitionCollector {
                 })
                 .map(|m| MethodDefinition {
                     name: m.name.value.clone(),
+                    location: self.location(m.name.span),
                     visibility: m.modifiers.visibility().into(),
                     modifiers: m
                         .modifiers
@@ -795,6 +894,7 @@ impl Visitor<()> for DefinitionCollector {
 
             self.collection.add_enum(EnumDefinition {
                 name,
+                location,
                 implements,
                 constants,
                 methods,
@@ -811,6 +911,7 @@ impl Visitor<()> for DefinitionCollector {
             ..
         }) = downcast::<BackedEnumStatement>(node)
         {
+            let location = self.location(name.span);
             let name = self.qualify_name(&name.value);
             let implements = implements
                 .iter()
@@ -845,6 +946,7 @@ impl Visitor<()> for DefinitionCollector {
                 })
                 .map(|m| MethodDefinition {
                     name: m.name.value.clone(),
+                    location: self.location(m.name.span),
                     visibility: m.modifiers.visibility().into(),
                     modifiers: m
                         .modifiers
@@ -892,6 +994,7 @@ impl Visitor<()> for DefinitionCollector {
 
             self.collection.add_enum(EnumDefinition {
                 name,
+                location,
                 implements,
                 constants,
                 methods,
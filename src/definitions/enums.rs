@@ -1,11 +1,14 @@
 use pxp_parser::{lexer::byte_string::ByteString, parser::ast::enums::BackedEnumType};
 use serde::{Deserialize, Serialize};
 
-use super::{constants::ConstantDefinition, functions::MethodDefinition};
+use super::{
+    constants::ConstantDefinition, functions::MethodDefinition, location::DefinitionLocation,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumDefinition {
     pub name: ByteString,
+    pub location: DefinitionLocation,
     pub implements: Vec<ByteString>,
     pub backed_type: Option<EnumBackedType>,
     pub members: Vec<ByteString>,
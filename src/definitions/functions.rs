@@ -3,11 +3,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::shared::{modifier::Modifier, types::Type, visibility::Visibility};
 
-use super::parameter::Parameter;
+use super::{location::DefinitionLocation, parameter::Parameter};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionDefinition {
     pub name: ByteString,
+    pub location: DefinitionLocation,
     pub parameters: Vec<Parameter>,
     pub return_type: Option<Type>,
 }
@@ -54,6 +55,7 @@ impl FunctionDefinition {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MethodDefinition {
     pub name: ByteString,
+    pub location: DefinitionLocation,
     pub visibility: Visibility,
     pub modifiers: Vec<Modifier>,
     pub parameters: Vec<Parameter>,
@@ -1,12 +1,94 @@
 use pxp_parser::lexer::byte_string::ByteString;
 use serde::{Deserialize, Serialize};
 
-use super::{constants::ConstantDefinition, functions::MethodDefinition};
+use crate::analyser::context::Context;
+
+use super::{
+    collection::DefinitionCollection, constants::ConstantDefinition, functions::MethodDefinition,
+    location::DefinitionLocation,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceDefinition {
     pub name: ByteString,
+    pub location: DefinitionLocation,
     pub extends: Vec<ByteString>,
     pub constants: Vec<ConstantDefinition>,
     pub methods: Vec<MethodDefinition>,
 }
+
+impl InterfaceDefinition {
+    /// Get every method that an implementation of this interface has to define,
+    /// including the methods of the interfaces that it extends, along with the
+    /// name of the interface that declares each method.
+    pub fn get_all_methods<'a>(
+        &'a self,
+        definitions: &'a DefinitionCollection,
+        context: &Context,
+    ) -> Vec<(&'a ByteString, &'a MethodDefinition)> {
+        let mut methods: Vec<(&ByteString, &MethodDefinition)> = Vec::new();
+        let mut visited = vec![&self.name];
+        let mut interfaces = vec![self];
+
+        while let Some(interface) = interfaces.pop() {
+            for method in interface.methods.iter() {
+                // Method names are case-insensitive.
+                if !methods
+                    .iter()
+                    .any(|(_, m)| m.name.eq_ignore_ascii_case(&method.name))
+                {
+                    methods.push((&interface.name, method));
+                }
+            }
+
+            for parent in interface.extends.iter() {
+                let parent = match definitions.get_interface(parent, context) {
+                    Some(parent) => parent,
+                    None => continue,
+                };
+
+                // Guard against interfaces that (indirectly) extend themselves.
+                if !visited.contains(&&parent.name) {
+                    visited.push(&parent.name);
+                    interfaces.push(parent);
+                }
+            }
+        }
+
+        methods
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pxp_parser::{lexer::byte_string::ByteString, parse};
+
+    use crate::{analyser::context::Context, definitions::collector::DefinitionCollector};
+
+    #[test]
+    fn it_collects_methods_from_parent_interfaces() {
+        let code = "<?php
+            interface A { function a(); function shared(); }
+            interface B extends A { function b(); function SHARED(); }
+            interface C extends B, C {}
+        ";
+
+        let mut ast = parse(code.as_bytes()).unwrap();
+        let mut collector = DefinitionCollector::new();
+        collector.scan(&mut ast);
+
+        let definitions = collector.collect();
+        let context = Context::default();
+        let interface = definitions
+            .get_interface(&ByteString::from(b"\\C"), &context)
+            .unwrap();
+
+        let methods = interface
+            .get_all_methods(&definitions, &context)
+            .iter()
+            .map(|(interface, method)| format!("{}::{}", interface, method.name))
+            .collect::<Vec<String>>();
+
+        assert_eq!(methods, vec!["\\B::b", "\\B::SHARED", "\\A::a"]);
+    }
+}
@@ -0,0 +1,14 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Where a definition was found, so that editors can jump to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DefinitionLocation {
+    /// The file that contains the definition. Definitions that come from the bundled
+    /// stubs don't have a file.
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
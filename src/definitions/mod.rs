@@ -5,6 +5,7 @@ pub mod constants;
 pub mod enums;
 pub mod functions;
 pub mod interfaces;
+pub mod location;
 pub mod parameter;
 pub mod property;
 pub mod traits;
@@ -5,12 +5,13 @@ use crate::analyser::context::Context;
 
 use super::{
     collection::DefinitionCollection, constants::ConstantDefinition, functions::MethodDefinition,
-    property::PropertyDefinition,
+    location::DefinitionLocation, property::PropertyDefinition,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitDefinition {
     pub name: ByteString,
+    pub location: DefinitionLocation,
     pub uses: Vec<ByteString>,
     pub constants: Vec<ConstantDefinition>,
     pub properties: Vec<PropertyDefinition>,
@@ -24,23 +25,26 @@ impl TraitDefinition {
         definitions: &'a DefinitionCollection,
         context: &Context,
     ) -> Option<&'a MethodDefinition> {
-        self.methods.iter().find(|m| m.name == *name).or_else(|| {
-            for trait_ in &self.uses {
-                let trait_ = definitions.get_trait(trait_, context);
-
-                if trait_.is_none() {
-                    continue;
-                }
-
-                let trait_ = trait_.unwrap();
-                let method = trait_.get_method(name, definitions, context);
-
-                if method.is_some() {
-                    return method;
+        self.methods
+            .iter()
+            .find(|m| m.name.eq_ignore_ascii_case(name))
+            .or_else(|| {
+                for trait_ in &self.uses {
+                    let trait_ = definitions.get_trait(trait_, context);
+
+                    if trait_.is_none() {
+                        continue;
+                    }
+
+                    let trait_ = trait_.unwrap();
+                    let method = trait_.get_method(name, definitions, context);
+
+                    if method.is_some() {
+                        return method;
+                    }
                 }
-            }
 
-            None
-        })
+                None
+            })
     }
 }
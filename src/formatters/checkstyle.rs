@@ -0,0 +1,40 @@
+use crate::analyser::messages::{MessageCollector, MessageSeverity};
+
+use super::escape_xml;
+
+pub fn format(message_collections: &[MessageCollector]) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle>\n");
+
+    for messages in message_collections {
+        if messages.is_empty() {
+            continue;
+        }
+
+        output.push_str(&format!(
+            "  <file name=\"{}\">\n",
+            escape_xml(messages.get_file())
+        ));
+
+        for message in messages.iter() {
+            let severity = match message.severity {
+                MessageSeverity::Error => "error",
+                MessageSeverity::Warning => "warning",
+                MessageSeverity::Note => "info",
+            };
+
+            output.push_str(&format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}\" />\n",
+                message.line,
+                message.column,
+                severity,
+                escape_xml(&message.message),
+                escape_xml(&message.identifier)
+            ));
+        }
+
+        output.push_str("  </file>\n");
+    }
+
+    output.push_str("</checkstyle>\n");
+    output
+}
@@ -0,0 +1,64 @@
+use crate::analyser::messages::{MessageCollector, MessageSeverity};
+
+pub fn format(message_collections: &[MessageCollector]) -> String {
+    let mut output = String::new();
+
+    for messages in message_collections {
+        for message in messages.iter() {
+            let command = match message.severity {
+                MessageSeverity::Error => "error",
+                MessageSeverity::Warning => "warning",
+                MessageSeverity::Note => "notice",
+            };
+
+            output.push_str(&format!(
+                "::{} file={},line={},col={},title={}::{}\n",
+                command,
+                escape_property(messages.get_file()),
+                message.line,
+                message.column,
+                escape_property(&message.identifier),
+                escape_data(&message.message)
+            ));
+        }
+    }
+
+    output
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyser::messages::{MessageCollector, MessageSpan};
+
+    use super::format;
+
+    #[test]
+    fn it_escapes_workflow_commands() {
+        let mut messages = MessageCollector::new("src/a,b.php".to_string());
+        messages.warning(
+            "ignore.unmatched",
+            "100% unmatched\nsee: docs",
+            MessageSpan {
+                line: 3,
+                column: 5,
+                ..MessageSpan::default()
+            },
+        );
+
+        assert_eq!(
+            format(&[messages]),
+            "::warning file=src/a%2Cb.php,line=3,col=5,title=ignore.unmatched::100%25 unmatched%0Asee: docs\n"
+        );
+    }
+}
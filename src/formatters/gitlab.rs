@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::analyser::messages::{MessageCollector, MessageSeverity};
+
+pub fn format(message_collections: &[MessageCollector]) -> String {
+    let mut issues = Vec::new();
+
+    for messages in message_collections {
+        // The number of times each message has been reported in the file so far.
+        let mut occurrences: HashMap<(&str, &str), usize> = HashMap::new();
+
+        for message in messages.iter() {
+            let occurrence = occurrences
+                .entry((message.identifier.as_str(), message.message.as_str()))
+                .or_default();
+            *occurrence += 1;
+
+            let severity = match message.severity {
+                MessageSeverity::Error => "major",
+                MessageSeverity::Warning => "minor",
+                MessageSeverity::Note => "info",
+            };
+
+            issues.push(json!({
+                "description": message.message,
+                "check_name": message.identifier,
+                "fingerprint": fingerprint(&[
+                    messages.get_file(),
+                    &message.identifier,
+                    &message.message,
+                    &occurrence.to_string(),
+                ]),
+                "severity": severity,
+                "location": {
+                    "path": messages.get_file(),
+                    "positions": {
+                        "begin": { "line": message.line, "column": message.column },
+                    },
+                },
+            }));
+        }
+    }
+
+    serde_json::to_string_pretty(&issues).unwrap()
+}
+
+/// GitLab uses the fingerprint to track issues between pipelines, so it has to be
+/// stable across runs and Rust versions. This is a 64-bit FNV-1a hash. The position
+/// of a message isn't part of it, since editing the lines above would change it.
+fn fingerprint(parts: &[&str]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::analyser::messages::{MessageCollector, MessageSpan};
+
+    use super::format;
+
+    fn fingerprints(lines: &[usize]) -> Vec<String> {
+        let mut messages = MessageCollector::new("src/a.php".to_string());
+
+        for line in lines {
+            messages.error(
+                "function.notFound",
+                "Function `helper` not found",
+                MessageSpan {
+                    line: *line,
+                    ..MessageSpan::default()
+                },
+            );
+        }
+
+        let issues: Value = serde_json::from_str(&format(&[messages])).unwrap();
+
+        issues
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|issue| issue["fingerprint"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn it_keeps_fingerprints_when_messages_move() {
+        assert_eq!(fingerprints(&[3, 7]), fingerprints(&[5, 12]));
+    }
+
+    #[test]
+    fn it_gives_repeated_messages_different_fingerprints() {
+        let fingerprints = fingerprints(&[3, 7]);
+
+        assert_ne!(fingerprints[0], fingerprints[1]);
+    }
+}
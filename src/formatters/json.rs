@@ -0,0 +1,5 @@
+use crate::analyser::messages::MessageCollector;
+
+pub fn format(message_collections: &[MessageCollector]) -> String {
+    serde_json::to_string_pretty(message_collections).unwrap()
+}
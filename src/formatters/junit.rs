@@ -0,0 +1,73 @@
+use crate::analyser::messages::MessageCollector;
+
+use super::escape_xml;
+
+/// Every analysed file is a test case, which fails if any messages were reported for it.
+pub fn format(message_collections: &[MessageCollector]) -> String {
+    let failures = message_collections
+        .iter()
+        .filter(|messages| !messages.is_empty())
+        .count();
+
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str(&format!(
+        "<testsuite name=\"statan\" tests=\"{}\" failures=\"{}\">\n",
+        message_collections.len(),
+        failures
+    ));
+
+    for messages in message_collections {
+        let file = escape_xml(messages.get_file());
+
+        if messages.is_empty() {
+            output.push_str(&format!("  <testcase name=\"{file}\" />\n"));
+            continue;
+        }
+
+        output.push_str(&format!("  <testcase name=\"{file}\">\n"));
+
+        for message in messages.iter() {
+            output.push_str(&format!(
+                "    <failure type=\"{}\" message=\"{}\">{}:{}:{}</failure>\n",
+                escape_xml(&message.identifier),
+                escape_xml(&message.message),
+                file,
+                message.line,
+                message.column
+            ));
+        }
+
+        output.push_str("  </testcase>\n");
+    }
+
+    output.push_str("</testsuite>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyser::messages::{MessageCollector, MessageSpan};
+
+    use super::format;
+
+    #[test]
+    fn it_counts_failing_files() {
+        let mut failing = MessageCollector::new("src/a.php".to_string());
+        failing.error(
+            "function.notFound",
+            "Function `a` not found",
+            MessageSpan::default(),
+        );
+        failing.error(
+            "function.notFound",
+            "Function `b` not found",
+            MessageSpan::default(),
+        );
+        let passing = MessageCollector::new("src/b.php".to_string());
+
+        let output = format(&[failing, passing]);
+
+        assert!(output.contains("<testsuite name=\"statan\" tests=\"2\" failures=\"1\">"));
+        assert!(output.contains("<testcase name=\"src/b.php\" />"));
+    }
+}
@@ -0,0 +1,92 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::analyser::messages::MessageCollector;
+
+mod checkstyle;
+mod github;
+mod gitlab;
+mod json;
+mod junit;
+mod sarif;
+mod table;
+
+/// The formats that the results of an analysis can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// Human readable tables, one per file.
+    #[default]
+    Table,
+    Json,
+    Checkstyle,
+    Junit,
+    /// SARIF 2.1.0, as understood by GitHub code scanning and most IDEs.
+    Sarif,
+    /// GitLab Code Quality reports.
+    Gitlab,
+    /// GitHub Actions workflow commands, which show up as annotations on pull requests.
+    Github,
+}
+
+impl ErrorFormat {
+    pub fn format(&self, message_collections: &[MessageCollector]) -> String {
+        match self {
+            ErrorFormat::Table => table::format(message_collections),
+            ErrorFormat::Json => json::format(message_collections),
+            ErrorFormat::Checkstyle => checkstyle::format(message_collections),
+            ErrorFormat::Junit => junit::format(message_collections),
+            ErrorFormat::Sarif => sarif::format(message_collections),
+            ErrorFormat::Gitlab => gitlab::format(message_collections),
+            ErrorFormat::Github => github::format(message_collections),
+        }
+    }
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(ErrorFormat::Table),
+            "json" => Ok(ErrorFormat::Json),
+            "checkstyle" => Ok(ErrorFormat::Checkstyle),
+            "junit" => Ok(ErrorFormat::Junit),
+            "sarif" => Ok(ErrorFormat::Sarif),
+            "gitlab" => Ok(ErrorFormat::Gitlab),
+            "github" => Ok(ErrorFormat::Github),
+            _ => Err(format!(
+                "unsupported error format {s}, expected one of table, json, checkstyle, junit, sarif, gitlab or github"
+            )),
+        }
+    }
+}
+
+impl Display for ErrorFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorFormat::Table => write!(f, "table"),
+            ErrorFormat::Json => write!(f, "json"),
+            ErrorFormat::Checkstyle => write!(f, "checkstyle"),
+            ErrorFormat::Junit => write!(f, "junit"),
+            ErrorFormat::Sarif => write!(f, "sarif"),
+            ErrorFormat::Gitlab => write!(f, "gitlab"),
+            ErrorFormat::Github => write!(f, "github"),
+        }
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,126 @@
+use std::{collections::BTreeSet, fs::read};
+
+use serde_json::{json, Value};
+
+use crate::analyser::messages::{Message, MessageCollector, MessageSeverity};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub fn format(message_collections: &[MessageCollector]) -> String {
+    let mut rules = BTreeSet::new();
+    let mut results = Vec::new();
+
+    for messages in message_collections {
+        // Columns and offsets are counted in UTF-16 code units, so the file is needed to
+        // convert the byte offsets of the messages. If it can't be read, they're used as-is.
+        let contents = read(messages.get_file()).ok();
+
+        for message in messages.iter() {
+            rules.insert(message.identifier.as_str());
+
+            let level = match message.severity {
+                MessageSeverity::Error => "error",
+                MessageSeverity::Warning => "warning",
+                MessageSeverity::Note => "note",
+            };
+
+            results.push(json!({
+                "ruleId": message.identifier,
+                "level": level,
+                "message": { "text": message.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": messages.get_file().replace('\\', "/") },
+                        "region": region(message, contents.as_deref()),
+                    },
+                }],
+            }));
+        }
+    }
+
+    let rules: Vec<Value> = rules.into_iter().map(|id| json!({ "id": id })).collect();
+
+    let log = json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "Statan",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/pxp-lang/statan",
+                    "rules": rules,
+                },
+            },
+            "columnKind": "utf16CodeUnits",
+            "results": results,
+        }],
+    });
+
+    serde_json::to_string_pretty(&log).unwrap()
+}
+
+fn region(message: &Message, contents: Option<&[u8]>) -> Value {
+    let contents = match contents {
+        Some(contents) => contents,
+        None => {
+            return json!({
+                "startLine": message.line,
+                "startColumn": message.column,
+                "charOffset": message.start,
+                "charLength": message.end - message.start,
+            })
+        }
+    };
+
+    let start = message.start.min(contents.len());
+    let end = message.end.clamp(start, contents.len());
+    let line_start = contents[..start]
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+
+    json!({
+        "startLine": message.line,
+        "startColumn": utf16_length(&contents[line_start..start]) + 1,
+        "charOffset": utf16_length(&contents[..start]),
+        "charLength": utf16_length(&contents[start..end]),
+    })
+}
+
+fn utf16_length(bytes: &[u8]) -> usize {
+    String::from_utf8_lossy(bytes).encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyser::messages::{MessageCollector, MessageSpan};
+
+    use super::region;
+
+    #[test]
+    fn it_converts_byte_offsets_to_utf16_code_units() {
+        let contents = "<?php\n$café = '😀' . foo();\n";
+        let start = contents.find("foo").unwrap();
+
+        let mut messages = MessageCollector::new("src/a.php".to_string());
+        messages.error(
+            "function.notFound",
+            "Function `foo` not found",
+            MessageSpan {
+                line: 2,
+                column: start - 6 + 1,
+                start,
+                end: start + "foo".len(),
+            },
+        );
+        let message = messages.iter().next().unwrap();
+
+        let region = region(message, Some(contents.as_bytes()));
+
+        assert_eq!(region["startLine"], 2);
+        assert_eq!(region["startColumn"], 16);
+        assert_eq!(region["charOffset"], 21);
+        assert_eq!(region["charLength"], 3);
+    }
+}
@@ -0,0 +1,83 @@
+use std::fs::read;
+
+use colored::*;
+use prettytable::{row, Table};
+
+use crate::analyser::messages::{Message, MessageCollector};
+
+pub fn format(message_collections: &[MessageCollector]) -> String {
+    let mut output = String::new();
+
+    for messages in message_collections {
+        if messages.is_empty() {
+            continue;
+        }
+
+        let contents = read(messages.get_file()).unwrap_or_default();
+
+        let mut table = Table::new();
+        table.add_row(row!["Line", messages.get_file()]);
+        for message in messages.iter() {
+            let mut text = message.message.clone();
+
+            if let Some(excerpt) = excerpt(&contents, message) {
+                text.push('\n');
+                text.push_str(&excerpt);
+            }
+
+            text.push('\n');
+            text.push_str(&message.identifier.dimmed().to_string());
+
+            table.add_row(row![format!("{}:{}", message.line, message.column), text]);
+        }
+
+        output.push_str(&table.to_string());
+    }
+
+    output
+}
+
+/// The line that the message was reported on, with the offending code underlined.
+fn excerpt(contents: &[u8], message: &Message) -> Option<String> {
+    let line = contents
+        .split(|byte| *byte == b'\n')
+        .nth(message.line.checked_sub(1)?)?;
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+    let column = message.column.checked_sub(1)?;
+    if column > line.len() {
+        return None;
+    }
+
+    // Leading indentation is left out to keep the excerpt compact.
+    let indentation = line
+        .iter()
+        .take_while(|byte| byte.is_ascii_whitespace())
+        .count()
+        .min(column);
+
+    let length = if message.end > message.start {
+        (message.end - message.start).min(line.len() - column)
+    } else {
+        // Only the start of the code is known, so underline the word at that position.
+        line[column..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'$' | b'\\'))
+            .count()
+    };
+
+    // Keep tabs in the padding so that the carets line up with the code above them,
+    // and only count the first byte of multi-byte characters.
+    let padding: String = line[indentation..column]
+        .iter()
+        .filter(|byte| **byte & 0xC0 != 0x80)
+        .map(|byte| if *byte == b'\t' { '\t' } else { ' ' })
+        .collect();
+
+    Some(format!(
+        "{}\n{}{}",
+        String::from_utf8_lossy(&line[indentation..]),
+        padding,
+        "^".repeat(length.max(1)).red()
+    ))
+}
@@ -0,0 +1,235 @@
+use std::{collections::BTreeMap, ops::RangeInclusive, path::PathBuf, process::Command};
+
+use crate::project::normalise;
+
+/// The lines of a file that changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangedLines {
+    /// The whole file is new.
+    All,
+    Ranges(Vec<RangeInclusive<usize>>),
+}
+
+impl ChangedLines {
+    pub fn contains(&self, line: usize) -> bool {
+        match self {
+            ChangedLines::All => true,
+            ChangedLines::Ranges(ranges) => ranges.iter().any(|range| range.contains(&line)),
+        }
+    }
+}
+
+/// Ask git for the files that were added or modified since the given reference,
+/// including changes that haven't been committed yet and untracked files. Changes are
+/// compared to the commit that the current branch forked from, so that changes made
+/// to the reference since then don't count. Paths are relative to the current
+/// directory, and only files inside of it are returned.
+pub fn changed_files(reference: &str) -> Result<BTreeMap<PathBuf, ChangedLines>, String> {
+    let base = git(&["merge-base", reference, "HEAD"])?;
+
+    let diff = git(&[
+        "-c",
+        "core.quotePath=false",
+        "diff",
+        "--relative",
+        "--no-color",
+        "--no-ext-diff",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        "--diff-filter=ACMR",
+        "-U0",
+        base.trim(),
+    ])?;
+    let mut files = parse_diff(&diff);
+
+    let untracked = git(&["ls-files", "-z", "--others", "--exclude-standard"])?;
+
+    for path in untracked.split('\0').filter(|path| !path.is_empty()) {
+        files.insert(normalise(&PathBuf::from(path)), ChangedLines::All);
+    }
+
+    Ok(files)
+}
+
+/// Find the lines that were added or changed in each file of a diff.
+fn parse_diff(diff: &str) -> BTreeMap<PathBuf, ChangedLines> {
+    let mut files = BTreeMap::new();
+    let mut current = None;
+
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            // Every file in the diff starts with a `+++ b/path` line.
+            current = parse_path(path)
+                .and_then(|path| path.strip_prefix("b/").map(str::to_string))
+                .map(|path| normalise(&PathBuf::from(path)));
+
+            if let Some(path) = &current {
+                files.insert(path.clone(), ChangedLines::Ranges(Vec::new()));
+            }
+        } else if let Some(hunk) = line.strip_prefix("@@ ") {
+            let (path, range) = match (&current, parse_hunk(hunk)) {
+                (Some(path), Some(range)) => (path, range),
+                _ => continue,
+            };
+
+            if let Some(ChangedLines::Ranges(ranges)) = files.get_mut(path) {
+                ranges.push(range);
+            }
+        }
+    }
+
+    files
+}
+
+/// Parse a path from the header of a file in a diff. Git adds a tab after paths that
+/// contain a space, and quotes paths with special characters, e.g. `"b/caf\303\251.php"`.
+fn parse_path(path: &str) -> Option<String> {
+    let path = path.strip_suffix('\t').unwrap_or(path);
+
+    let quoted = match path
+        .strip_prefix('"')
+        .and_then(|path| path.strip_suffix('"'))
+    {
+        Some(quoted) => quoted,
+        None => return Some(path.to_string()),
+    };
+
+    let mut bytes = Vec::new();
+    let mut characters = quoted.chars();
+
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend(character.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+
+        match characters.next()? {
+            'a' => bytes.push(0x07),
+            'b' => bytes.push(0x08),
+            'f' => bytes.push(0x0c),
+            'n' => bytes.push(b'\n'),
+            'r' => bytes.push(b'\r'),
+            't' => bytes.push(b'\t'),
+            'v' => bytes.push(0x0b),
+            // Other bytes are escaped as three octal digits.
+            digit @ '0'..='7' => {
+                let octal = [Some(digit), characters.next(), characters.next()]
+                    .into_iter()
+                    .collect::<Option<String>>()?;
+                bytes.push(u8::from_str_radix(&octal, 8).ok()?);
+            }
+            character => bytes.push(character as u8),
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+/// Parse the range of new lines from a hunk header, e.g. `-12,3 +14,5 @@`. Hunks
+/// that only remove lines don't have any new lines.
+fn parse_hunk(hunk: &str) -> Option<RangeInclusive<usize>> {
+    let added = hunk.split_whitespace().find(|part| part.starts_with('+'))?;
+    let mut parts = added[1..].split(',');
+
+    let start = parts.next()?.parse::<usize>().ok()?;
+    let count = match parts.next() {
+        Some(count) => count.parse::<usize>().ok()?,
+        None => 1,
+    };
+
+    if count == 0 {
+        return None;
+    }
+
+    Some(start..=start + count - 1)
+}
+
+fn git(arguments: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(arguments)
+        .output()
+        .map_err(|error| format!("failed to run git: {error}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            arguments.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{parse_diff, parse_hunk, parse_path, ChangedLines};
+
+    #[test]
+    fn it_parses_the_new_lines_of_a_hunk() {
+        assert_eq!(parse_hunk("-12,3 +14,5 @@"), Some(14..=18));
+        assert_eq!(parse_hunk("-12 +14 @@ function foo()"), Some(14..=14));
+        assert_eq!(parse_hunk("-12,3 +11,0 @@"), None);
+    }
+
+    #[test]
+    fn it_unquotes_paths() {
+        assert_eq!(
+            parse_path("b/src/Foo.php"),
+            Some("b/src/Foo.php".to_string())
+        );
+        assert_eq!(
+            parse_path("b/src/My File.php\t"),
+            Some("b/src/My File.php".to_string())
+        );
+        assert_eq!(
+            parse_path("\"b/src/caf\\303\\251.php\""),
+            Some("b/src/café.php".to_string())
+        );
+        assert_eq!(
+            parse_path("\"b/src/\\\"quoted\\\".php\""),
+            Some("b/src/\"quoted\".php".to_string())
+        );
+    }
+
+    #[test]
+    fn it_finds_the_changed_lines_of_each_file() {
+        let diff = "diff --git a/src/Foo.php b/src/Foo.php
+index 1111111..2222222 100644
+--- a/src/Foo.php
++++ b/src/Foo.php
+@@ -3,0 +4,2 @@ class Foo
++    public $a;
++    public $b;
+@@ -10 +12 @@ class Foo
+-        return 1;
++        return 2;
+@@ -20,2 +21,0 @@ class Foo
+-        // removed
+-        // removed
+diff --git a/src/New File.php b/src/New File.php
+new file mode 100644
+--- /dev/null
++++ b/src/New File.php\t
+@@ -0,0 +1,3 @@
++<?php
++
++echo 1;
+";
+
+        let files = parse_diff(diff);
+
+        assert_eq!(
+            files.get(&PathBuf::from("src/Foo.php")),
+            Some(&ChangedLines::Ranges(vec![4..=5, 12..=12]))
+        );
+        assert_eq!(
+            files.get(&PathBuf::from("src/New File.php")),
+            Some(&ChangedLines::Ranges(vec![1..=3]))
+        );
+        assert_eq!(files.len(), 2);
+    }
+}
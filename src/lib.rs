@@ -1,4 +1,12 @@
 pub mod analyser;
+pub mod baseline;
+pub mod composer;
+pub mod config;
 pub mod definitions;
+pub mod formatters;
+pub mod git;
+pub mod project;
 pub mod rules;
+pub mod runner;
 pub mod shared;
+pub mod stubs;
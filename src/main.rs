@@ -1,4 +1,7 @@
+use std::process::ExitCode;
+
 use clap::{Parser, Subcommand};
+use statan::{analyser::messages::MessageSeverity, formatters::ErrorFormat, stubs::PhpVersion};
 
 mod cmd;
 
@@ -12,18 +15,116 @@ struct Arguments {
 enum Command {
     #[clap(about = "Analyse a file.")]
     Analyse(AnalyseCommand),
+
+    #[clap(about = "Start a language server that communicates over stdio.")]
+    Lsp(LspCommand),
 }
 
 #[derive(Debug, Parser)]
 pub struct AnalyseCommand {
-    #[clap(help = "The file to analyse.")]
-    file: String,
+    #[clap(
+        value_name = "PATHS",
+        help = "The files, directories or glob patterns to analyse, e.g. 'src/**/*Controller.php'. Defaults to the paths in the configuration file."
+    )]
+    paths: Vec<String>,
+
+    #[clap(
+        long,
+        value_name = "PATTERN",
+        help = "A file, directory or glob pattern to leave out of the analysis. Can be used more than once."
+    )]
+    exclude: Vec<String>,
+
+    #[clap(
+        short,
+        long,
+        help = "The configuration file to use. Defaults to the nearest statan.toml file."
+    )]
+    config: Option<String>,
+
+    #[clap(
+        long,
+        help = "The PHP version to use when loading the bundled stubs. Defaults to the latest supported version."
+    )]
+    php_version: Option<PhpVersion>,
+
+    #[clap(
+        short,
+        long,
+        help = "The strictness level, from 0 to 9. Defaults to the strictest level."
+    )]
+    level: Option<u8>,
+
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "Write the current errors to a baseline file, so that future runs only report new errors. Defaults to the baseline file in the configuration."
+    )]
+    generate_baseline: Option<Option<String>>,
+
+    #[clap(
+        long,
+        default_value_t = ErrorFormat::Table,
+        help = "The format to write the results in: table, json, checkstyle, junit, sarif, gitlab or github."
+    )]
+    error_format: ErrorFormat,
+
+    #[clap(
+        long,
+        default_value = "error",
+        help = "The least severe kind of message that makes the command fail: error, warning or note."
+    )]
+    fail_on: MessageSeverity,
+
+    #[clap(
+        short,
+        long,
+        help = "Include backtraces in the messages reported for internal errors."
+    )]
+    verbose: bool,
+
+    #[clap(
+        long,
+        help = "Keep running and analyse the project again whenever a file changes."
+    )]
+    watch: bool,
+
+    #[clap(
+        long,
+        value_name = "REF",
+        help = "Only analyse the files that were added or modified since the given git reference, e.g. origin/main."
+    )]
+    changed_since: Option<String>,
+
+    #[clap(
+        long,
+        requires = "changed_since",
+        help = "Only report messages on the lines that changed since the --changed-since reference."
+    )]
+    changed_lines: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct LspCommand {
+    #[clap(
+        short,
+        long,
+        help = "The configuration file to use. Defaults to the nearest statan.toml file."
+    )]
+    config: Option<String>,
+
+    #[clap(
+        long,
+        help = "The PHP version to use when loading the bundled stubs. Defaults to the latest supported version."
+    )]
+    php_version: Option<PhpVersion>,
 }
 
-fn main() {
+fn main() -> ExitCode {
     let arguments = Arguments::parse();
 
     match arguments.command {
         Command::Analyse(args) => cmd::analyse::run(args),
+        Command::Lsp(args) => cmd::lsp::run(args),
     }
 }
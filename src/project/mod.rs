@@ -0,0 +1,400 @@
+use std::{
+    collections::HashSet,
+    fs::read,
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use pxp_parser::{lexer::byte_string::ByteString, parse, parser::ast::Statement};
+use rayon::prelude::*;
+
+use crate::{
+    analyser::{messages::MessageCollector, panics, Analyser},
+    definitions::{collection::DefinitionCollection, collector::DefinitionCollector},
+};
+
+pub mod patterns;
+
+#[derive(Debug)]
+pub struct ProjectFile {
+    path: PathBuf,
+    contents: Vec<u8>,
+    ast: Option<Vec<Statement>>,
+    parse_error: Option<String>,
+    references: Vec<ByteString>,
+    function_references: Vec<ByteString>,
+    /// Whether the file is only scanned for definitions and never analysed, e.g. a
+    /// file in the vendor directory that defines a class the project uses.
+    scan_only: bool,
+}
+
+impl ProjectFile {
+    pub fn new(path: PathBuf, contents: Vec<u8>) -> Self {
+        let mut file = Self {
+            path,
+            contents,
+            ast: None,
+            parse_error: None,
+            references: Vec::new(),
+            function_references: Vec::new(),
+            scan_only: false,
+        };
+
+        file.parse();
+        file
+    }
+
+    pub fn load(path: PathBuf) -> io::Result<Self> {
+        let contents = read(&path)?;
+
+        Ok(Self::new(path, contents))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn name(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+
+    pub fn contents(&self) -> &[u8] {
+        &self.contents
+    }
+
+    /// The fully qualified names of the classes that are instantiated or called
+    /// statically in this file, or that its definitions extend, implement or use
+    /// as a type.
+    pub fn references(&self) -> &[ByteString] {
+        &self.references
+    }
+
+    /// The fully qualified names of the functions that are called in this file.
+    pub fn function_references(&self) -> &[ByteString] {
+        &self.function_references
+    }
+
+    pub fn is_scan_only(&self) -> bool {
+        self.scan_only
+    }
+
+    pub fn parse_error(&self) -> Option<&str> {
+        self.parse_error.as_deref()
+    }
+
+    pub fn has_ast(&self) -> bool {
+        self.ast.is_some()
+    }
+
+    /// Drop the parsed AST for this file. The AST will be re-created from the
+    /// original contents if the file is analysed later on.
+    pub fn drop_ast(&mut self) {
+        self.ast = None;
+    }
+
+    fn parse(&mut self) {
+        match panics::catch(|| parse(&self.contents)) {
+            Ok(Ok(ast)) => {
+                self.ast = Some(ast);
+                self.parse_error = None;
+            }
+            Ok(Err(error)) => {
+                self.ast = None;
+                self.parse_error = Some(error.to_string());
+            }
+            Err(panic) => {
+                self.ast = None;
+                self.parse_error = Some(format!("internal error while parsing: {}", panic.message));
+            }
+        }
+    }
+
+    fn collect_definitions(&mut self) -> DefinitionCollection {
+        let mut collector = DefinitionCollector::for_file(self.path.clone());
+
+        if let Some(ast) = self.ast.as_mut() {
+            // If the collector crashes, the file's definitions are simply missing. Any
+            // problem with the file itself is reported when it's analysed.
+            if panics::catch(|| collector.scan(ast)).is_err() {
+                collector = DefinitionCollector::for_file(self.path.clone());
+            }
+        }
+
+        self.references = collector.references().to_vec();
+        self.function_references = collector.function_references().to_vec();
+        collector.collect()
+    }
+
+    fn analyse(&mut self, analyser: &mut Analyser) -> MessageCollector {
+        let name = self.name();
+
+        match self.ast.as_mut() {
+            Some(ast) => analyser.analyse_ast(name, &self.contents, ast),
+            // Files that failed to parse, or had their AST dropped, are handed back
+            // to the analyser as raw bytes so that it can parse them (again) and report
+            // any parse errors in the usual way.
+            None => analyser.analyse(name, &self.contents),
+        }
+    }
+}
+
+/// A set of project files that are read and parsed exactly once, then shared
+/// between the definition discovery and analysis phases.
+#[derive(Debug, Default)]
+pub struct Project {
+    files: Vec<ProjectFile>,
+}
+
+impl Project {
+    pub fn new() -> Self {
+        Self { files: Vec::new() }
+    }
+
+    /// Read and parse the given files in parallel. Paths are normalised and
+    /// de-duplicated, and the files are stored in path order.
+    pub fn load(paths: Vec<PathBuf>) -> io::Result<Self> {
+        let mut paths = paths
+            .iter()
+            .map(|path| normalise(path))
+            .collect::<Vec<PathBuf>>();
+        paths.sort();
+        paths.dedup();
+
+        let files = paths
+            .into_par_iter()
+            .map(ProjectFile::load)
+            .collect::<io::Result<Vec<ProjectFile>>>()?;
+
+        Ok(Self { files })
+    }
+
+    pub fn files(&self) -> &[ProjectFile] {
+        &self.files
+    }
+
+    pub fn get_file(&self, path: &Path) -> Option<&ProjectFile> {
+        let path = normalise(path);
+
+        self.files.iter().find(|file| file.path == path)
+    }
+
+    /// Scan every parsed file for definitions. Each file is scanned on its own
+    /// and the results are merged in path order, so the final collection does
+    /// not depend on how the work was scheduled.
+    pub fn collect_definitions(&mut self) -> DefinitionCollection {
+        let scanned = self
+            .files
+            .par_iter_mut()
+            .map(|file| file.collect_definitions())
+            .collect::<Vec<DefinitionCollection>>();
+
+        let mut collection = DefinitionCollection::new();
+
+        for definitions in scanned {
+            collection.merge(definitions);
+        }
+
+        collection
+    }
+
+    /// Load the definitions for classes that are referenced by the project but not
+    /// defined anywhere in it. The given resolver maps a fully qualified class name
+    /// to the file that should define it, e.g. using Composer's PSR-4 mappings.
+    ///
+    /// Resolved files are only scanned for definitions and never analysed, and any
+    /// classes they reference in turn are resolved in the same way.
+    pub fn resolve_missing_definitions(
+        &mut self,
+        collection: &mut DefinitionCollection,
+        resolver: impl Fn(&ByteString) -> Option<PathBuf> + Sync,
+    ) {
+        let mut attempted = HashSet::new();
+
+        loop {
+            let mut missing = collection.referenced_classes();
+            missing.extend(
+                self.files
+                    .iter()
+                    .flat_map(|file| file.references.iter().cloned()),
+            );
+            missing.retain(|name| !collection.has_classish(name) && attempted.insert(name.clone()));
+
+            let mut paths = missing
+                .par_iter()
+                .filter_map(&resolver)
+                .map(|path| normalise(&path))
+                .filter(|path| self.get_file(path).is_none())
+                .collect::<Vec<PathBuf>>();
+            paths.sort();
+            paths.dedup();
+
+            if paths.is_empty() {
+                break;
+            }
+
+            // Files that can't be read are skipped, the classes they were expected to
+            // define will be reported as missing during analysis instead.
+            let files = paths
+                .into_par_iter()
+                .filter_map(|path| ProjectFile::load(path).ok())
+                .map(|mut file| {
+                    let definitions = file.collect_definitions();
+
+                    // We don't follow references from the bodies of resolved files,
+                    // otherwise we'd end up loading most of the vendor directory.
+                    file.references.clear();
+                    file.function_references.clear();
+                    file.scan_only = true;
+                    file.drop_ast();
+
+                    (file, definitions)
+                })
+                .collect::<Vec<(ProjectFile, DefinitionCollection)>>();
+
+            for (file, definitions) in files {
+                collection.merge(definitions);
+                self.files.push(file);
+            }
+
+            self.files.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+    }
+
+    /// Read the file at the given path again, or add it to the project if it's new,
+    /// and return the definitions that it contains. If the file can no longer be
+    /// read, it's removed from the project instead.
+    pub fn reload_file(&mut self, path: &Path) -> Option<DefinitionCollection> {
+        let path = normalise(path);
+        let scan_only = self.get_file(&path).is_some_and(|file| file.scan_only);
+        self.files.retain(|file| file.path != path);
+
+        let mut file = ProjectFile::load(path).ok()?;
+        let definitions = file.collect_definitions();
+
+        if scan_only {
+            file.references.clear();
+            file.function_references.clear();
+            file.scan_only = true;
+            file.drop_ast();
+        }
+
+        let index = self
+            .files
+            .binary_search_by(|other| other.path.cmp(&file.path))
+            .unwrap_or_else(|index| index);
+        self.files.insert(index, file);
+
+        Some(definitions)
+    }
+
+    /// Keep the ASTs of files that match the given predicate and drop the rest.
+    /// Callers can use this to bound memory usage once definitions have been
+    /// collected and only a subset of the project is going to be analysed.
+    pub fn retain_asts(&mut self, predicate: impl Fn(&Path) -> bool) {
+        for file in self.files.iter_mut() {
+            if !predicate(&file.path) {
+                file.drop_ast();
+            }
+        }
+    }
+
+    /// Analyse every file that matches the given predicate in parallel, leaving out
+    /// the files that are only scanned for definitions. A new analyser is created
+    /// for each worker thread, and the results are returned in path order.
+    pub fn analyse(
+        &mut self,
+        predicate: impl Fn(&Path) -> bool + Sync + Send,
+        create_analyser: impl Fn() -> Analyser + Sync + Send,
+        on_file_analysed: impl Fn(&Path) + Sync + Send,
+    ) -> Vec<MessageCollector> {
+        self.files
+            .par_iter_mut()
+            .filter(|file| !file.scan_only && predicate(&file.path))
+            .map_init(&create_analyser, |analyser, file| {
+                let messages = file.analyse(analyser);
+                on_file_analysed(&file.path);
+                messages
+            })
+            .collect()
+    }
+}
+
+/// Find all of the files with the given extensions in the given paths. Paths that
+/// point to a file are included as-is, directories are searched recursively and
+/// paths that don't exist are skipped.
+pub fn discover(paths: &[PathBuf], extensions: &[String]) -> Result<Vec<PathBuf>, String> {
+    let extensions = extensions.iter().map(String::as_str).collect::<Vec<&str>>();
+    let mut files = Vec::new();
+
+    for path in paths {
+        if path.is_file() {
+            files.push(path.clone());
+        } else if path.is_dir() {
+            let directory = path.to_str().ok_or_else(|| {
+                format!(
+                    "failed to search {}: the path is not valid UTF-8",
+                    path.display()
+                )
+            })?;
+
+            let found = discoverer::discover(&extensions, &[directory])
+                .map_err(|error| format!("failed to search {}: {error}", path.display()))?;
+
+            files.extend(found);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Strip any `.` components from the given path so that `./src/Foo.php` and
+/// `src/Foo.php` are treated as the same file.
+pub fn normalise(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::write, process, sync::Arc};
+
+    use crate::{analyser::Analyser, definitions::collection::DefinitionCollection};
+
+    use super::{Project, ProjectFile};
+
+    #[test]
+    fn it_only_scans_resolved_files() {
+        let vendor = temp_dir().join(format!("statan-{}-Client.php", process::id()));
+        write(
+            &vendor,
+            "<?php namespace Vendor; class Client { function send() { helper(); } }",
+        )
+        .unwrap();
+
+        let mut project = Project {
+            files: vec![ProjectFile::new(
+                "src/main.php".into(),
+                b"<?php new \\Vendor\\Client();".to_vec(),
+            )],
+        };
+        let mut definitions = project.collect_definitions();
+        project.resolve_missing_definitions(&mut definitions, |_| Some(vendor.clone()));
+
+        let resolved = project.get_file(&vendor).unwrap();
+        assert!(resolved.is_scan_only());
+        assert!(resolved.function_references().is_empty());
+
+        let messages = project.analyse(
+            |_| true,
+            || Analyser::new(Arc::new(DefinitionCollection::default())),
+            |_| {},
+        );
+        let files = messages
+            .iter()
+            .map(|messages| messages.get_file())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(files, vec!["src/main.php"]);
+    }
+}
@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+
+use globset::{GlobBuilder, GlobMatcher};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use super::normalise;
+
+const GLOB_CHARACTERS: [char; 4] = ['*', '?', '[', '{'];
+
+/// A path or glob pattern that selects files, e.g. `src` or `src/**/*Controller.php`.
+#[derive(Debug, Clone)]
+pub enum PathPattern {
+    /// Matches the file itself, or everything inside of the directory.
+    Path(PathBuf),
+    /// Matches the files and directories that match the glob. The base is the part of
+    /// the pattern before the first wildcard, which is where matching files are found.
+    Glob { base: PathBuf, matcher: GlobMatcher },
+}
+
+impl PathPattern {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let path = normalise(Path::new(pattern));
+
+        if !pattern.contains(GLOB_CHARACTERS) {
+            return Ok(Self::Path(path));
+        }
+
+        // `*` doesn't match across directories, so `src/*.php` only matches the files
+        // directly inside of `src` and `src/**/*.php` matches all of them.
+        let matcher = GlobBuilder::new(&path.to_string_lossy())
+            .literal_separator(true)
+            .build()
+            .map_err(|error| format!("invalid pattern {pattern}: {error}"))?
+            .compile_matcher();
+
+        let base = path
+            .components()
+            .take_while(|component| {
+                !component
+                    .as_os_str()
+                    .to_string_lossy()
+                    .contains(GLOB_CHARACTERS)
+            })
+            .collect();
+
+        Ok(Self::Glob { base, matcher })
+    }
+
+    /// The path to search for matching files in.
+    pub fn base(&self) -> PathBuf {
+        let base = match self {
+            Self::Path(path) => path,
+            Self::Glob { base, .. } => base,
+        };
+
+        if base.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            base.clone()
+        }
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        match self {
+            Self::Path(pattern) => path.starts_with(pattern),
+            // A glob can match one of the file's directories, e.g. `src/*/Tests`.
+            Self::Glob { matcher, .. } => path.ancestors().any(|path| matcher.is_match(path)),
+        }
+    }
+}
+
+/// Decides which of the project's files are analysed, based on the patterns to
+/// include and exclude and on the project's `.gitignore` file.
+#[derive(Debug)]
+pub struct PathFilter {
+    root: PathBuf,
+    /// The absolute path of the root, for matching absolute paths against the
+    /// `.gitignore` file.
+    canonical_root: Option<PathBuf>,
+    includes: Vec<PathPattern>,
+    excludes: Vec<PathPattern>,
+    gitignore: Gitignore,
+}
+
+impl PathFilter {
+    pub fn new(root: &Path, includes: Vec<PathPattern>, excludes: Vec<PathPattern>) -> Self {
+        // Both files are optional, and invalid lines in them are skipped.
+        let mut builder = GitignoreBuilder::new(root);
+        builder.add(root.join(".gitignore"));
+        builder.add(root.join(".git/info/exclude"));
+
+        Self {
+            root: normalise(root),
+            canonical_root: root.canonicalize().ok(),
+            includes,
+            excludes,
+            gitignore: builder.build().unwrap_or_else(|_| Gitignore::empty()),
+        }
+    }
+
+    /// The paths that need to be searched to find every file that can match.
+    pub fn base_paths(&self) -> Vec<PathBuf> {
+        self.includes.iter().map(PathPattern::base).collect()
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        self.includes.iter().any(|pattern| pattern.matches(path))
+            && !self.excludes.iter().any(|pattern| pattern.matches(path))
+            && !self.is_ignored(path)
+    }
+
+    /// Whether the file is ignored by git. Files outside of the project are never ignored.
+    fn is_ignored(&self, path: &Path) -> bool {
+        let relative = match path.strip_prefix(&self.root) {
+            Ok(relative) if !relative.has_root() => relative.to_path_buf(),
+            // The root is empty when it's the working directory, so an absolute path
+            // is matched against the absolute path of the root instead.
+            _ => match (&self.canonical_root, path.canonicalize()) {
+                (Some(root), Ok(path)) => match path.strip_prefix(root) {
+                    Ok(relative) => relative.to_path_buf(),
+                    Err(_) => return false,
+                },
+                _ => return false,
+            },
+        };
+
+        self.gitignore
+            .matched_path_or_any_parents(relative, false)
+            .is_ignore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::current_dir, path::Path};
+
+    use super::{PathFilter, PathPattern};
+
+    #[test]
+    fn it_matches_paths_inside_of_a_directory() {
+        let pattern = PathPattern::new("./src").unwrap();
+
+        assert!(pattern.matches(Path::new("src/lib.rs")));
+        assert!(!pattern.matches(Path::new("tests/lib.rs")));
+        assert_eq!(pattern.base(), Path::new("src"));
+    }
+
+    #[test]
+    fn it_matches_globs_against_files_and_directories() {
+        let pattern = PathPattern::new("src/*/Tests").unwrap();
+
+        assert!(pattern.matches(Path::new("src/Http/Tests/RouterTest.php")));
+        assert!(!pattern.matches(Path::new("src/Http/Router.php")));
+        assert_eq!(pattern.base(), Path::new("src"));
+
+        let pattern = PathPattern::new("src/*.php").unwrap();
+
+        assert!(pattern.matches(Path::new("src/helpers.php")));
+        assert!(!pattern.matches(Path::new("src/Http/Router.php")));
+    }
+
+    #[test]
+    fn it_uses_the_current_directory_as_the_base_of_an_empty_pattern() {
+        assert_eq!(PathPattern::new("").unwrap().base(), Path::new("."));
+    }
+
+    #[test]
+    fn it_excludes_paths() {
+        let filter = PathFilter::new(
+            Path::new("."),
+            vec![PathPattern::new("src").unwrap()],
+            vec![PathPattern::new("src/stubs").unwrap()],
+        );
+
+        assert!(filter.matches(Path::new("src/lib.rs")));
+        assert!(!filter.matches(Path::new("src/stubs/mod.rs")));
+        assert!(!filter.matches(Path::new("build.rs")));
+    }
+
+    #[test]
+    fn it_matches_absolute_paths_against_the_gitignore_file() {
+        let current = current_dir().unwrap();
+        let filter = PathFilter::new(
+            Path::new("."),
+            vec![PathPattern::new("").unwrap()],
+            Vec::new(),
+        );
+
+        assert!(filter.matches(Path::new("src/lib.rs")));
+        assert!(filter.matches(&current.join("src/lib.rs")));
+        assert!(!filter.matches(&current.join("target/CACHEDIR.TAG")));
+    }
+}
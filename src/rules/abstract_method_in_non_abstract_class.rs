@@ -5,7 +5,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
 };
 
@@ -15,6 +18,10 @@ use super::Rule;
 pub struct AbstractMethodInNonAbstractClassRule;
 
 impl Rule for AbstractMethodInNonAbstractClassRule {
+    fn name(&self) -> &'static str {
+        "AbstractMethodInNonAbstractClassRule"
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         match downcast(node) {
             Some(ClassMember::AbstractMethod(_)) => true,
@@ -22,7 +29,7 @@ impl Rule for AbstractMethodInNonAbstractClassRule {
         }
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -45,7 +52,7 @@ impl Rule for AbstractMethodInNonAbstractClassRule {
                 current_class
                     .get_method(&name.value, definitions, context)
                     .unwrap(),
-                name.span,
+                MessageSpan::with_length(name.span, name.value.len()),
             ),
             _ => unreachable!(),
         };
@@ -55,11 +62,12 @@ impl Rule for AbstractMethodInNonAbstractClassRule {
         }
 
         messages.error(
+            "method.abstractInNonAbstractClass",
             format!(
                 "Non-abstract class {} contains abstract method {}",
                 current_class.name, method_definition.name
             ),
-            span.line,
+            span,
         );
     }
 }
@@ -8,7 +8,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
 };
 
@@ -18,11 +21,19 @@ use super::Rule;
 pub struct CallPrivateThroughStaticRule;
 
 impl Rule for CallPrivateThroughStaticRule {
+    fn name(&self) -> &'static str {
+        "CallPrivateThroughStaticRule"
+    }
+
+    fn level(&self) -> u8 {
+        2
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         downcast::<StaticMethodCallExpression>(node).is_some()
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -40,7 +51,9 @@ impl Rule for CallPrivateThroughStaticRule {
         }
 
         let (method_name, span) = match &static_method_call_expression.method {
-            Identifier::SimpleIdentifier(SimpleIdentifier { span, value }) => (value, span),
+            Identifier::SimpleIdentifier(SimpleIdentifier { span, value }) => {
+                (value, MessageSpan::with_length(*span, value.len()))
+            }
             _ => return,
         };
 
@@ -64,11 +77,12 @@ impl Rule for CallPrivateThroughStaticRule {
         }
 
         messages.error(
+            "staticClassAccess.privateMethod",
             format!(
                 "Unsafe call to private method {}::{}() on static::",
                 current_class.name, method_name,
             ),
-            span.line,
+            span,
         );
     }
 }
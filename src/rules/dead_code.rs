@@ -0,0 +1,707 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use pxp_parser::{
+    downcast::downcast,
+    lexer::{byte_string::ByteString, token::Span},
+    node::Node,
+    parser::ast::{
+        classes::{ClassMember, ClassStatement},
+        functions::{ArrowFunctionExpression, ClosureExpression},
+        identifiers::{Identifier, SimpleIdentifier},
+        literals::{Literal, LiteralString},
+        modifiers::Visibility,
+        try_block::{CatchBlock, CatchType},
+        variables::{SimpleVariable, Variable},
+        ConstantFetchExpression, Expression, FunctionCallExpression, InstanceofExpression,
+        MethodCallExpression, NewExpression, NullsafeMethodCallExpression,
+        NullsafePropertyFetchExpression, PropertyFetchExpression, StaticMethodCallExpression,
+        StaticPropertyFetchExpression,
+    },
+};
+use regex::Regex;
+
+use crate::{
+    analyser::{
+        collected::{CollectedData, ProjectMessages},
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
+    config::DeadCodeConfiguration,
+    definitions::collection::DefinitionCollection,
+    shared::types::Type,
+};
+
+use super::{ProjectRule, Rule};
+
+/// Reports private methods, properties and constants that are never used inside of
+/// their class. When unused public code is reported, it also collects the names
+/// that each file uses for `UnusedPublicCodeRule`.
+#[derive(Debug, Default)]
+pub struct DeadCodeRule {
+    classes: Vec<ClassMembers>,
+    used: UsedNames,
+    collect_used_names: bool,
+}
+
+/// The private members of a class that's being analysed, and the members that
+/// have been used inside of it so far.
+#[derive(Debug, Default)]
+struct ClassMembers {
+    name: ByteString,
+    methods: Vec<(ByteString, Span)>,
+    properties: Vec<(ByteString, Span)>,
+    constants: Vec<(ByteString, Span)>,
+    used: UsedNames,
+    /// Whether a method or property is accessed with a dynamic name, e.g. `$this->$name()`,
+    /// in which case any of them could be used.
+    dynamic_access: bool,
+    /// Whether the class uses a trait, whose methods could use any of the members.
+    uses_traits: bool,
+}
+
+/// The names used by a file, collected for `UnusedPublicCodeRule`.
+#[derive(Debug, Default, Clone)]
+pub struct UsedNames {
+    /// The fully qualified names of the functions that are called.
+    pub functions: HashSet<ByteString>,
+    /// The fully qualified names of the classes that are referenced.
+    pub classes: HashSet<ByteString>,
+    /// The lowercase names of the methods that are called, on any class.
+    pub methods: HashSet<Vec<u8>>,
+    /// The names of the properties and constants that are used, on any class.
+    pub properties: HashSet<Vec<u8>>,
+    pub constants: HashSet<Vec<u8>>,
+    /// String literals, since they can refer to any of the above, e.g. `[$this, 'handle']`.
+    pub strings: HashSet<Vec<u8>>,
+}
+
+impl UsedNames {
+    fn add_method(&mut self, name: &ByteString) {
+        self.methods.insert(name.to_ascii_lowercase());
+    }
+
+    fn add_property(&mut self, name: &ByteString) {
+        self.properties
+            .insert(name.strip_prefix(b"$").unwrap_or(name).to_vec());
+    }
+
+    fn add_constant(&mut self, name: &ByteString) {
+        self.constants.insert(name.to_vec());
+    }
+
+    fn add_string(&mut self, value: &ByteString) {
+        self.strings.insert(value.to_vec());
+        self.methods.insert(value.to_ascii_lowercase());
+        self.properties.insert(value.to_vec());
+        self.constants.insert(value.to_vec());
+    }
+}
+
+impl DeadCodeRule {
+    pub fn new(configuration: &DeadCodeConfiguration) -> Self {
+        Self {
+            collect_used_names: configuration.report_unused_public,
+            ..Self::default()
+        }
+    }
+
+    /// Record that a member was used, both in the enclosing class and in the file.
+    fn use_member(&mut self, add: impl Fn(&mut UsedNames)) {
+        if let Some(class) = self.classes.last_mut() {
+            add(&mut class.used);
+        }
+
+        add(&mut self.used);
+    }
+
+    fn use_dynamic_member(&mut self) {
+        if let Some(class) = self.classes.last_mut() {
+            class.dynamic_access = true;
+        }
+    }
+
+    fn use_class(
+        &mut self,
+        target: &Expression,
+        definitions: &DefinitionCollection,
+        context: &Context,
+    ) {
+        if let Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+            value,
+            ..
+        })) = target
+        {
+            self.use_class_name(value, definitions, context);
+        }
+    }
+
+    fn use_class_name(
+        &mut self,
+        name: &ByteString,
+        definitions: &DefinitionCollection,
+        context: &Context,
+    ) {
+        let name = definitions
+            .get_class(name, context)
+            .map(|class| class.name.clone())
+            .unwrap_or_else(|| context.resolve_name(name));
+
+        self.used.classes.insert(name);
+    }
+}
+
+impl Rule for DeadCodeRule {
+    fn name(&self) -> &'static str {
+        "DeadCodeRule"
+    }
+
+    fn level(&self) -> u8 {
+        4
+    }
+
+    fn should_run(&self, node: &dyn Node) -> bool {
+        downcast::<ClassStatement>(node).is_some()
+            || downcast::<FunctionCallExpression>(node).is_some()
+            || downcast::<NewExpression>(node).is_some()
+            || downcast::<InstanceofExpression>(node).is_some()
+            || downcast::<MethodCallExpression>(node).is_some()
+            || downcast::<NullsafeMethodCallExpression>(node).is_some()
+            || downcast::<StaticMethodCallExpression>(node).is_some()
+            || downcast::<PropertyFetchExpression>(node).is_some()
+            || downcast::<NullsafePropertyFetchExpression>(node).is_some()
+            || downcast::<StaticPropertyFetchExpression>(node).is_some()
+            || downcast::<ConstantFetchExpression>(node).is_some()
+            || downcast::<Literal>(node).is_some()
+            || downcast::<CatchBlock>(node).is_some()
+            || downcast::<ClosureExpression>(node).is_some()
+            || downcast::<ArrowFunctionExpression>(node).is_some()
+    }
+
+    fn before_file(
+        &mut self,
+        _definitions: &DefinitionCollection,
+        _messages: &mut MessageCollector,
+    ) {
+        self.classes.clear();
+        self.used = UsedNames::default();
+    }
+
+    fn enter_node(
+        &mut self,
+        node: &mut dyn Node,
+        definitions: &DefinitionCollection,
+        _messages: &mut MessageCollector,
+        context: &mut Context,
+    ) {
+        if let Some(ClassStatement { name, body, .. }) = downcast::<ClassStatement>(node) {
+            let mut class = ClassMembers {
+                name: context.resolve_name(&name.value),
+                ..ClassMembers::default()
+            };
+
+            for member in body.members.iter() {
+                match member {
+                    ClassMember::ConcreteMethod(method)
+                        if matches!(method.modifiers.visibility(), Visibility::Private) =>
+                    {
+                        class
+                            .methods
+                            .push((method.name.value.clone(), method.name.span));
+                    }
+                    ClassMember::Property(property)
+                        if matches!(property.modifiers.visibility(), Visibility::Private) =>
+                    {
+                        for entry in property.entries.iter() {
+                            let variable = entry.variable();
+                            class
+                                .properties
+                                .push((variable.name.clone(), variable.span));
+                        }
+                    }
+                    ClassMember::Constant(constant)
+                        if matches!(constant.modifiers.visibility(), Visibility::Private) =>
+                    {
+                        for entry in constant.entries.iter() {
+                            class
+                                .constants
+                                .push((entry.name.value.clone(), entry.name.span));
+                        }
+                    }
+                    ClassMember::TraitUsage(_) => class.uses_traits = true,
+                    _ => {}
+                }
+            }
+
+            self.classes.push(class);
+            return;
+        }
+
+        if let Some(FunctionCallExpression { target, .. }) =
+            downcast::<FunctionCallExpression>(node)
+        {
+            if let Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                value,
+                ..
+            })) = target.as_ref()
+            {
+                let name = definitions
+                    .get_function(value, context)
+                    .map(|function| function.name.clone())
+                    .unwrap_or_else(|| context.resolve_name(value));
+
+                self.used.functions.insert(name);
+            }
+
+            return;
+        }
+
+        if let Some(CatchBlock { types, .. }) = downcast::<CatchBlock>(node) {
+            let identifiers = match types {
+                CatchType::Identifier { identifier } => vec![identifier],
+                CatchType::Union { identifiers } => identifiers.iter().collect(),
+            };
+
+            for SimpleIdentifier { value, .. } in identifiers {
+                self.use_class_name(value, definitions, context);
+            }
+
+            return;
+        }
+
+        // The signatures of functions and methods are collected with the definitions,
+        // but closures are only found in their bodies.
+        let signature = if let Some(ClosureExpression {
+            parameters,
+            return_type,
+            ..
+        }) = downcast::<ClosureExpression>(node)
+        {
+            Some((parameters, return_type))
+        } else if let Some(ArrowFunctionExpression {
+            parameters,
+            return_type,
+            ..
+        }) = downcast::<ArrowFunctionExpression>(node)
+        {
+            Some((parameters, return_type))
+        } else {
+            None
+        };
+
+        if let Some((parameters, return_type)) = signature {
+            let types = parameters
+                .iter()
+                .filter_map(|parameter| parameter.data_type.as_ref())
+                .chain(return_type.iter().map(|return_type| &return_type.data_type))
+                .map(Type::from)
+                .collect::<Vec<Type>>();
+
+            for name in types.iter().flat_map(Type::referenced_names) {
+                self.use_class_name(name, definitions, context);
+            }
+
+            return;
+        }
+
+        if let Some(NewExpression { target, .. }) = downcast::<NewExpression>(node) {
+            self.use_class(target, definitions, context);
+            return;
+        }
+
+        if let Some(InstanceofExpression { right, .. }) = downcast::<InstanceofExpression>(node) {
+            self.use_class(right, definitions, context);
+            return;
+        }
+
+        let method = if let Some(MethodCallExpression { method, .. }) =
+            downcast::<MethodCallExpression>(node)
+        {
+            Some(method.as_ref())
+        } else if let Some(NullsafeMethodCallExpression { method, .. }) =
+            downcast::<NullsafeMethodCallExpression>(node)
+        {
+            Some(method.as_ref())
+        } else {
+            None
+        };
+
+        if let Some(method) = method {
+            match method {
+                Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                    value,
+                    ..
+                })) => self.use_member(|used| used.add_method(value)),
+                _ => self.use_dynamic_member(),
+            }
+
+            return;
+        }
+
+        let property = if let Some(PropertyFetchExpression { property, .. }) =
+            downcast::<PropertyFetchExpression>(node)
+        {
+            Some(property.as_ref())
+        } else if let Some(NullsafePropertyFetchExpression { property, .. }) =
+            downcast::<NullsafePropertyFetchExpression>(node)
+        {
+            Some(property.as_ref())
+        } else {
+            None
+        };
+
+        if let Some(property) = property {
+            match property {
+                Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
+                    value,
+                    ..
+                })) => self.use_member(|used| used.add_property(value)),
+                _ => self.use_dynamic_member(),
+            }
+
+            return;
+        }
+
+        if let Some(StaticMethodCallExpression { target, method, .. }) =
+            downcast::<StaticMethodCallExpression>(node)
+        {
+            self.use_class(target, definitions, context);
+
+            match method {
+                Identifier::SimpleIdentifier(SimpleIdentifier { value, .. }) => {
+                    self.use_member(|used| used.add_method(value))
+                }
+                _ => self.use_dynamic_member(),
+            }
+
+            return;
+        }
+
+        if let Some(StaticPropertyFetchExpression {
+            target, property, ..
+        }) = downcast::<StaticPropertyFetchExpression>(node)
+        {
+            self.use_class(target, definitions, context);
+
+            match property {
+                Variable::SimpleVariable(SimpleVariable { name, .. }) => {
+                    self.use_member(|used| used.add_property(name))
+                }
+                _ => self.use_dynamic_member(),
+            }
+
+            return;
+        }
+
+        if let Some(ConstantFetchExpression {
+            target, constant, ..
+        }) = downcast::<ConstantFetchExpression>(node)
+        {
+            self.use_class(target, definitions, context);
+
+            if let Identifier::SimpleIdentifier(SimpleIdentifier { value, .. }) = constant {
+                self.use_member(|used| used.add_constant(value));
+            }
+
+            return;
+        }
+
+        if let Some(Literal::String(LiteralString { value, .. })) = downcast::<Literal>(node) {
+            self.use_member(|used| used.add_string(value));
+        }
+    }
+
+    fn leave_node(
+        &mut self,
+        node: &mut dyn Node,
+        _definitions: &DefinitionCollection,
+        messages: &mut MessageCollector,
+        _context: &mut Context,
+    ) {
+        if downcast::<ClassStatement>(node).is_none() {
+            return;
+        }
+
+        let class = match self.classes.pop() {
+            Some(class) => class,
+            None => return,
+        };
+
+        // The trait's methods aren't analysed as part of the class, so we can't tell
+        // which of the class's members they use.
+        if class.uses_traits {
+            return;
+        }
+
+        if !class.dynamic_access {
+            for (method, span) in class.methods.iter() {
+                // Magic methods and constructors are called by PHP itself.
+                if method.starts_with(b"__") {
+                    continue;
+                }
+
+                if !class.used.methods.contains(&method.to_ascii_lowercase()) {
+                    messages.warning(
+                        "method.unused",
+                        format!("Private method {}::{}() is never used", class.name, method),
+                        MessageSpan::with_length(*span, method.len()),
+                    );
+                }
+            }
+
+            for (property, span) in class.properties.iter() {
+                let name = property.strip_prefix(b"$").unwrap_or(property);
+
+                if !class.used.properties.contains(name) {
+                    messages.warning(
+                        "property.unused",
+                        format!(
+                            "Private property {}::{} is never used",
+                            class.name, property
+                        ),
+                        MessageSpan::with_length(*span, property.len()),
+                    );
+                }
+            }
+        }
+
+        for (constant, span) in class.constants.iter() {
+            if !class.used.constants.contains(&constant.to_vec()) {
+                messages.warning(
+                    "classConstant.unused",
+                    format!(
+                        "Private constant {}::{} is never used",
+                        class.name, constant
+                    ),
+                    MessageSpan::with_length(*span, constant.len()),
+                );
+            }
+        }
+    }
+
+    fn after_file(&mut self, _definitions: &DefinitionCollection, messages: &mut MessageCollector) {
+        if self.collect_used_names {
+            messages.collect(std::mem::take(&mut self.used));
+        }
+    }
+}
+
+/// Reports public functions, classes and methods in the analysed files that are
+/// never used by any of the analysed files. This is opt-in, since code that's only
+/// used by a framework or by other projects looks unused too.
+#[derive(Debug)]
+pub struct UnusedPublicCodeRule {
+    enabled: bool,
+    entry_points: Vec<Regex>,
+}
+
+impl UnusedPublicCodeRule {
+    pub fn new(configuration: &DeadCodeConfiguration) -> Self {
+        Self {
+            enabled: configuration.report_unused_public,
+            entry_points: configuration
+                .entry_points
+                .iter()
+                .map(|pattern| entry_point_pattern(pattern))
+                .collect(),
+        }
+    }
+
+    /// Whether the given name, e.g. `App\Http\Controllers\HomeController::index`, is
+    /// used from outside of the analysed code.
+    fn is_entry_point(&self, name: &str) -> bool {
+        let name = name.trim_start_matches('\\');
+
+        self.entry_points
+            .iter()
+            .any(|pattern| pattern.is_match(name))
+    }
+}
+
+/// Turn an entry point pattern into a regular expression, where `*` matches any
+/// characters and everything else is matched literally.
+fn entry_point_pattern(pattern: &str) -> Regex {
+    let pattern = pattern.trim_start_matches('\\');
+    let pattern = pattern
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<String>>()
+        .join(".*");
+
+    Regex::new(&format!("^{pattern}$")).unwrap()
+}
+
+impl ProjectRule for UnusedPublicCodeRule {
+    fn name(&self) -> &'static str {
+        "UnusedPublicCodeRule"
+    }
+
+    fn level(&self) -> u8 {
+        4
+    }
+
+    fn run(
+        &self,
+        data: &CollectedData,
+        definitions: &DefinitionCollection,
+        messages: &mut ProjectMessages,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut files = HashSet::new();
+        let mut used = UsedNames::default();
+
+        for (file, names) in data.get::<UsedNames>() {
+            files.insert(file);
+            used.functions.extend(names.functions.iter().cloned());
+            used.classes.extend(names.classes.iter().cloned());
+            used.methods.extend(names.methods.iter().cloned());
+            used.strings.extend(names.strings.iter().cloned());
+        }
+
+        // Parent classes, interfaces and types in signatures count as uses too.
+        used.classes.extend(definitions.referenced_classes());
+
+        // Strings can name a function or class, e.g. callables and `class_exists` checks.
+        let used_in_string = |name: &ByteString| {
+            used.strings
+                .contains(name.strip_prefix(b"\\").unwrap_or(name))
+        };
+
+        let file_of = |file: &Option<PathBuf>| {
+            file.as_ref()
+                .map(|file| file.to_string_lossy().to_string())
+                .filter(|file| files.contains(file.as_str()))
+        };
+
+        for function in definitions.functions() {
+            let file = match file_of(&function.location.file) {
+                Some(file) => file,
+                None => continue,
+            };
+
+            if used.functions.contains(&function.name)
+                || used_in_string(&function.name)
+                || self.is_entry_point(&function.name.to_string())
+            {
+                continue;
+            }
+
+            messages.file(&file).warning(
+                "function.unused",
+                format!("Function {}() is never used", function.name),
+                &function.location,
+            );
+        }
+
+        for class in definitions.classes() {
+            let file = match file_of(&class.location.file) {
+                Some(file) => file,
+                None => continue,
+            };
+
+            let class_name = class.name.to_string();
+
+            if !used.classes.contains(&class.name)
+                && !used_in_string(&class.name)
+                && !self.is_entry_point(&class_name)
+            {
+                messages.file(&file).warning(
+                    "class.unused",
+                    format!("Class {class_name} is never used"),
+                    &class.location,
+                );
+
+                continue;
+            }
+
+            for method in class.methods.iter() {
+                if !method.is_public()
+                    || method.name.starts_with(b"__")
+                    || used.methods.contains(&method.name.to_ascii_lowercase())
+                    || self.is_entry_point(&format!("{class_name}::{}", method.name))
+                {
+                    continue;
+                }
+
+                messages.file(&file).warning(
+                    "method.unused",
+                    format!(
+                        "Public method {class_name}::{}() is never used",
+                        method.name
+                    ),
+                    &method.location,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        analyser::Analyser, config::DeadCodeConfiguration,
+        definitions::collection::DefinitionCollection,
+    };
+
+    use super::{entry_point_pattern, DeadCodeRule, UsedNames};
+
+    fn used_classes(code: &str) -> Vec<String> {
+        let configuration = DeadCodeConfiguration {
+            report_unused_public: true,
+            ..DeadCodeConfiguration::default()
+        };
+
+        let mut analyser = Analyser::new(Arc::new(DefinitionCollection::default()));
+        analyser.add_rule(Box::new(DeadCodeRule::new(&configuration)));
+
+        let messages = analyser.analyse("test.php".to_string(), code.as_bytes());
+        let mut classes = messages
+            .collected()
+            .iter()
+            .filter_map(|data| data.downcast_ref::<UsedNames>())
+            .flat_map(|used| used.classes.iter().map(|name| name.to_string()))
+            .collect::<Vec<String>>();
+        classes.sort();
+        classes
+    }
+
+    #[test]
+    fn it_matches_entry_points() {
+        let pattern = entry_point_pattern("\\App\\Http\\Controllers\\*");
+
+        assert!(pattern.is_match("App\\Http\\Controllers\\HomeController::index"));
+        assert!(!pattern.is_match("App\\Models\\User"));
+
+        let pattern = entry_point_pattern("*::handle");
+
+        assert!(pattern.is_match("App\\Jobs\\SendInvoice::handle"));
+        assert!(!pattern.is_match("App\\Jobs\\SendInvoice::handler"));
+    }
+
+    #[test]
+    fn it_collects_classes_used_in_catch_blocks() {
+        let code = "<?php
+        try {
+            run();
+        } catch (NotFound | Invalid $e) {
+        }";
+
+        assert_eq!(used_classes(code), vec!["\\Invalid", "\\NotFound"]);
+    }
+
+    #[test]
+    fn it_collects_classes_used_in_closure_signatures() {
+        let code = "<?php
+        $handler = function (Request $request): Response {};
+        $mapper = fn (?User $user): Profile|Team => $user;";
+
+        assert_eq!(
+            used_classes(code),
+            vec!["\\Profile", "\\Request", "\\Response", "\\Team", "\\User"]
+        );
+    }
+}
@@ -7,7 +7,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
 };
 
@@ -17,11 +20,15 @@ use super::Rule;
 pub struct DumpTypeRule;
 
 impl Rule for DumpTypeRule {
+    fn name(&self) -> &'static str {
+        "DumpTypeRule"
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         downcast::<FunctionCallExpression>(node).is_some()
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -41,19 +48,26 @@ impl Rule for DumpTypeRule {
             return;
         }
 
+        let span = MessageSpan::between(
+            function_call_expression.arguments.left_parenthesis,
+            function_call_expression.arguments.right_parenthesis,
+        );
+
         let argument = match function_call_expression.arguments.arguments.first() {
             Some(Argument::Positional(argument)) => argument,
             Some(Argument::Named(_)) => {
                 messages.error(
+                    "dumpType.arguments",
                     "dumpType() does not support named arguments",
-                    function_call_expression.arguments.left_parenthesis.line,
+                    span,
                 );
                 return;
             }
             None => {
                 messages.error(
+                    "dumpType.arguments",
                     "dumpType() requires an argument",
-                    function_call_expression.arguments.left_parenthesis.line,
+                    span,
                 );
                 return;
             }
@@ -61,9 +75,6 @@ impl Rule for DumpTypeRule {
 
         let ty = context.get_type(&argument.value, definitions);
 
-        messages.note(
-            format!("Dumped type: {ty}"),
-            function_call_expression.arguments.left_parenthesis.line,
-        );
+        messages.note("dumpType.type", format!("Dumped type: {ty}"), span);
     }
 }
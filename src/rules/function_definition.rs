@@ -5,7 +5,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
 };
 
@@ -15,11 +18,19 @@ use super::Rule;
 pub struct FunctionDefinitionRule;
 
 impl Rule for FunctionDefinitionRule {
+    fn name(&self) -> &'static str {
+        "FunctionDefinitionRule"
+    }
+
+    fn level(&self) -> u8 {
+        6
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         downcast::<FunctionStatement>(node).is_some()
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -33,19 +44,22 @@ impl Rule for FunctionDefinitionRule {
             match &parameter.data_type {
                 Some(ty) => match ty {
                     ParsedType::Void(span) => messages.warning(
+                        "parameter.void",
                         format!("Parameter {} has invalid type void.", parameter.name),
-                        span.line,
+                        MessageSpan::with_length(*span, "void".len()),
                     ),
                     ParsedType::Never(span) => messages.warning(
+                        "parameter.never",
                         format!("Parameter {} has invalid type never.", parameter.name),
-                        span.line,
+                        MessageSpan::with_length(*span, "never".len()),
                     ),
                     _ => {}
                 },
                 None => {
                     messages.warning(
+                        "missingType.parameter",
                         format!("Parameter {} has no type.", parameter.name),
-                        parameter.name.span.line,
+                        MessageSpan::with_length(parameter.name.span, parameter.name.name.len()),
                     );
                     continue;
                 }
@@ -53,15 +67,19 @@ impl Rule for FunctionDefinitionRule {
 
             if let Some(Expression::Null) = parameter.default {
                 if !type_is_nullable(parameter.data_type.as_ref().unwrap()) {
-                    messages.warning(format!("Parameter {} has a default value of null, but does not have a nullable type.", parameter.name), parameter.name.span.line);
+                    messages.warning("parameter.defaultNull", format!("Parameter {} has a default value of null, but does not have a nullable type.", parameter.name), MessageSpan::with_length(parameter.name.span, parameter.name.name.len()));
                 }
             }
         }
 
         if function_statement.return_type.is_none() {
             messages.warning(
+                "missingType.return",
                 format!("Function {} has no return type.", function_statement.name),
-                function_statement.name.span.line,
+                MessageSpan::with_length(
+                    function_statement.name.span,
+                    function_statement.name.value.len(),
+                ),
             );
         }
     }
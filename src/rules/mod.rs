@@ -3,28 +3,128 @@ use std::fmt::Debug;
 use pxp_parser::node::Node;
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        collected::{CollectedData, ProjectMessages},
+        context::Context,
+        messages::MessageCollector,
+    },
+    config::Configuration,
     definitions::collection::DefinitionCollection,
 };
 
 pub mod abstract_method_in_non_abstract_class;
 pub mod call_private_through_static;
+pub mod dead_code;
 pub mod dump_type;
 pub mod function_definition;
+pub mod unreachable_code;
 pub mod valid_arithmetic_operation;
 pub mod valid_assignment;
 pub mod valid_class;
 pub mod valid_function;
+pub mod valid_implementation;
 pub mod valid_static_call;
 pub mod valid_this_call;
 
-pub trait Rule: Debug {
+/// The strictest level that rules can be grouped into.
+pub const MAX_LEVEL: u8 = 9;
+
+pub trait Rule: Debug + Send {
+    fn name(&self) -> &'static str;
+
+    /// The strictness level that this rule belongs to. Rules are only run when the
+    /// configured level is greater than or equal to their own level, so level 0
+    /// rules are always run.
+    fn level(&self) -> u8 {
+        0
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool;
-    fn run(
+
+    /// Called for each node that the rule should run on, before the node's children
+    /// are visited.
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
         messages: &mut MessageCollector,
         context: &mut Context,
     );
+
+    /// Called for each node that the rule should run on, after the node's children
+    /// have been visited. The context is the same one that `enter_node` was given,
+    /// so any scope that the node opened, e.g. a function body, has been left again.
+    fn leave_node(
+        &mut self,
+        _node: &mut dyn Node,
+        _definitions: &DefinitionCollection,
+        _messages: &mut MessageCollector,
+        _context: &mut Context,
+    ) {
+    }
+
+    /// Called before each file is analysed. The same rule instance is used for every
+    /// file that an analyser analyses, so rules that keep state between nodes should
+    /// reset it here.
+    fn before_file(
+        &mut self,
+        _definitions: &DefinitionCollection,
+        _messages: &mut MessageCollector,
+    ) {
+    }
+
+    /// Called once every node in the file has been visited, to report messages about
+    /// the file as a whole.
+    fn after_file(
+        &mut self,
+        _definitions: &DefinitionCollection,
+        _messages: &mut MessageCollector,
+    ) {
+    }
+}
+
+/// A rule that reports messages about the project as a whole, such as classes that
+/// are never used. Project rules run once every file has been analysed, using the
+/// data that rules collected with `MessageCollector::collect`.
+pub trait ProjectRule: Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// The strictness level that this rule belongs to, see `Rule::level`.
+    fn level(&self) -> u8 {
+        0
+    }
+
+    fn run(
+        &self,
+        data: &CollectedData,
+        definitions: &DefinitionCollection,
+        messages: &mut ProjectMessages,
+    );
+}
+
+/// Get an instance of every built-in rule, set up with the rules' options from the
+/// given configuration.
+pub fn all(configuration: &Configuration) -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(valid_assignment::ValidAssignmentRule),
+        Box::new(dump_type::DumpTypeRule),
+        Box::new(valid_function::ValidFunctionRule),
+        Box::new(valid_class::ValidClassRule),
+        Box::new(valid_static_call::ValidStaticCallRule),
+        Box::new(valid_this_call::ValidThisCallRule),
+        Box::new(abstract_method_in_non_abstract_class::AbstractMethodInNonAbstractClassRule),
+        Box::new(valid_implementation::ValidImplementationRule),
+        Box::new(call_private_through_static::CallPrivateThroughStaticRule),
+        Box::new(function_definition::FunctionDefinitionRule),
+        Box::new(valid_arithmetic_operation::ValidArithmeticOperationRule),
+        Box::new(unreachable_code::UnreachableCodeRule::default()),
+        Box::new(dead_code::DeadCodeRule::new(&configuration.rules.dead_code)),
+    ]
+}
+
+/// Get an instance of every built-in project rule.
+pub fn project(configuration: &Configuration) -> Vec<Box<dyn ProjectRule>> {
+    vec![Box::new(dead_code::UnusedPublicCodeRule::new(
+        &configuration.rules.dead_code,
+    ))]
 }
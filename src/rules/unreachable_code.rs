@@ -0,0 +1,324 @@
+use pxp_parser::{
+    downcast::downcast,
+    lexer::token::Span,
+    node::Node,
+    parser::ast::{
+        control_flow::{IfStatement, IfStatementBody},
+        identifiers::SimpleIdentifier,
+        literals::{Literal, LiteralFloat, LiteralInteger, LiteralString},
+        loops::{
+            BreakStatement, ContinueStatement, DoWhileStatement, ForStatement, ForeachStatement,
+            WhileStatement,
+        },
+        variables::SimpleVariable,
+        BoolExpression, EchoStatement, Expression, ExpressionStatement, ReturnStatement, Statement,
+    },
+};
+
+use crate::{
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
+    definitions::collection::DefinitionCollection,
+    shared::types::Type,
+};
+
+use super::Rule;
+
+/// Reports statements that can never run, because they follow a statement that
+/// always leaves the current block, and branches that can never be taken because
+/// their condition is always `true` or `false`.
+///
+/// Every node gets a frame, and a statement's siblings are the other statements in
+/// its parent's frame. Once a statement that always leaves the block has been
+/// visited, the first statement after it in the same frame is reported.
+#[derive(Debug, Default)]
+pub struct UnreachableCodeRule {
+    frames: Vec<Frame>,
+}
+
+#[derive(Debug, Default)]
+struct Frame {
+    /// The kind of statement that left the block, e.g. `return`.
+    left_by: Option<&'static str>,
+    reported: bool,
+}
+
+impl Rule for UnreachableCodeRule {
+    fn name(&self) -> &'static str {
+        "UnreachableCodeRule"
+    }
+
+    fn level(&self) -> u8 {
+        4
+    }
+
+    fn should_run(&self, _node: &dyn Node) -> bool {
+        true
+    }
+
+    fn before_file(
+        &mut self,
+        _definitions: &DefinitionCollection,
+        _messages: &mut MessageCollector,
+    ) {
+        self.frames.clear();
+        self.frames.push(Frame::default());
+    }
+
+    fn enter_node(
+        &mut self,
+        node: &mut dyn Node,
+        definitions: &DefinitionCollection,
+        messages: &mut MessageCollector,
+        context: &mut Context,
+    ) {
+        if let Some(statement) = downcast::<Statement>(node) {
+            // A label can be jumped to with `goto`, and declarations are hoisted.
+            let reachable = matches!(statement, Statement::Label(_)) || is_declaration(statement);
+            let frame = self.frames.last_mut().unwrap();
+
+            if matches!(statement, Statement::Label(_)) {
+                *frame = Frame::default();
+            }
+
+            if let (Some(left_by), false, false) = (frame.left_by, frame.reported, reachable) {
+                if let Some(span) = first_span(node) {
+                    frame.reported = true;
+                    messages.error(
+                        "deadCode.unreachable",
+                        format!("Unreachable statement after {left_by}"),
+                        span,
+                    );
+                }
+            }
+        }
+
+        if let Some(IfStatement {
+            r#if,
+            condition,
+            body,
+            ..
+        }) = downcast::<IfStatement>(node)
+        {
+            let has_else = match body {
+                IfStatementBody::Statement {
+                    elseifs, r#else, ..
+                } => !elseifs.is_empty() || r#else.is_some(),
+                IfStatementBody::Block {
+                    elseifs, r#else, ..
+                } => !elseifs.is_empty() || r#else.is_some(),
+            };
+
+            match constant_condition(condition, definitions, context) {
+                Some(Type::False) => messages.error(
+                    "if.alwaysFalse",
+                    "If condition is always false, so its body is unreachable",
+                    MessageSpan::with_length(*r#if, "if".len()),
+                ),
+                Some(Type::True) if has_else => messages.error(
+                    "if.alwaysTrue",
+                    "If condition is always true, so the branches after it are unreachable",
+                    MessageSpan::with_length(*r#if, "if".len()),
+                ),
+                _ => {}
+            }
+        }
+
+        if let Some(WhileStatement {
+            r#while, condition, ..
+        }) = downcast::<WhileStatement>(node)
+        {
+            if let Some(Type::False) = constant_condition(condition, definitions, context) {
+                messages.error(
+                    "while.alwaysFalse",
+                    "While condition is always false, so its body is unreachable",
+                    MessageSpan::with_length(*r#while, "while".len()),
+                );
+            }
+        }
+
+        self.frames.push(Frame::default());
+    }
+
+    fn leave_node(
+        &mut self,
+        node: &mut dyn Node,
+        definitions: &DefinitionCollection,
+        _messages: &mut MessageCollector,
+        context: &mut Context,
+    ) {
+        self.frames.pop();
+
+        let left_by = match downcast::<Statement>(node) {
+            Some(Statement::Return(_)) => Some("return"),
+            Some(Statement::Break(_)) => Some("break"),
+            Some(Statement::Continue(_)) => Some("continue"),
+            Some(Statement::Expression(ExpressionStatement { expression, .. })) => match expression
+            {
+                Expression::Throw(_) => Some("throw"),
+                Expression::Exit(_) => Some("exit"),
+                Expression::Die(_) => Some("die"),
+                _ if is_call(expression)
+                    && context.get_type(expression, definitions) == Type::Never =>
+                {
+                    Some("a call that never returns")
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let (Some(left_by), Some(frame)) = (left_by, self.frames.last_mut()) {
+            frame.left_by.get_or_insert(left_by);
+        }
+    }
+}
+
+/// Functions, classes and the like are defined before any code in the file runs,
+/// so they're never unreachable.
+fn is_declaration(statement: &Statement) -> bool {
+    matches!(
+        statement,
+        Statement::Function(_)
+            | Statement::Class(_)
+            | Statement::Interface(_)
+            | Statement::Trait(_)
+            | Statement::UnitEnum(_)
+            | Statement::BackedEnum(_)
+            | Statement::Use(_)
+            | Statement::GroupUse(_)
+            | Statement::Noop(_)
+            | Statement::HaltCompiler(_)
+    )
+}
+
+fn is_call(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::FunctionCall(_) | Expression::MethodCall(_) | Expression::StaticMethodCall(_)
+    )
+}
+
+/// The type of a condition that is always `true` or `false`. Only literals and calls
+/// are checked. The type of a variable is the one it was last assigned while walking
+/// the file, which isn't necessarily its type when a loop or branch runs.
+fn constant_condition(
+    condition: &Expression,
+    definitions: &DefinitionCollection,
+    context: &Context,
+) -> Option<Type> {
+    match condition {
+        Expression::Bool(BoolExpression { value: true, .. }) => return Some(Type::True),
+        Expression::Bool(BoolExpression { value: false, .. }) => return Some(Type::False),
+        _ if !is_call(condition) => return None,
+        _ => {}
+    }
+
+    match context.get_type(condition, definitions) {
+        ty @ (Type::True | Type::False) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Statements don't have a span of their own, so the span of the statement's keyword
+/// is used, or the first span found inside of the statement if it doesn't have one.
+fn first_span(node: &mut dyn Node) -> Option<Span> {
+    let keyword = match downcast::<Statement>(node) {
+        Some(Statement::Return(ReturnStatement { r#return, .. })) => Some(*r#return),
+        Some(Statement::Echo(EchoStatement { echo, .. })) => Some(*echo),
+        Some(Statement::Break(BreakStatement { r#break, .. })) => Some(*r#break),
+        Some(Statement::Continue(ContinueStatement { r#continue, .. })) => Some(*r#continue),
+        Some(Statement::If(IfStatement { r#if, .. })) => Some(*r#if),
+        Some(Statement::While(WhileStatement { r#while, .. })) => Some(*r#while),
+        Some(Statement::DoWhile(DoWhileStatement { r#do, .. })) => Some(*r#do),
+        Some(Statement::For(ForStatement { r#for, .. })) => Some(*r#for),
+        Some(Statement::Foreach(ForeachStatement { foreach, .. })) => Some(*foreach),
+        _ => None,
+    };
+
+    if keyword.is_some() {
+        return keyword;
+    }
+
+    if let Some(ReturnStatement { r#return, .. }) = downcast::<ReturnStatement>(node) {
+        return Some(*r#return);
+    }
+
+    if let Some(EchoStatement { echo, .. }) = downcast::<EchoStatement>(node) {
+        return Some(*echo);
+    }
+
+    if let Some(SimpleVariable { span, .. }) = downcast::<SimpleVariable>(node) {
+        return Some(*span);
+    }
+
+    if let Some(SimpleIdentifier { span, .. }) = downcast::<SimpleIdentifier>(node) {
+        return Some(*span);
+    }
+
+    match downcast::<Literal>(node) {
+        Some(Literal::String(LiteralString { span, .. }))
+        | Some(Literal::Integer(LiteralInteger { span, .. }))
+        | Some(Literal::Float(LiteralFloat { span, .. })) => return Some(*span),
+        _ => {}
+    }
+
+    node.children().into_iter().find_map(first_span)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use pxp_parser::parse;
+
+    use crate::{analyser::Analyser, definitions::collector::DefinitionCollector};
+
+    use super::UnreachableCodeRule;
+
+    fn analyse(code: &str) -> Vec<String> {
+        let mut ast = parse(code.as_bytes()).unwrap();
+        let mut collector = DefinitionCollector::new();
+        collector.scan(&mut ast);
+
+        let mut analyser = Analyser::new(Arc::new(collector.collect()));
+        analyser.add_rule(Box::new(UnreachableCodeRule::default()));
+        analyser
+            .analyse("test.php".to_string(), code.as_bytes())
+            .iter()
+            .map(|message| message.identifier.clone())
+            .collect()
+    }
+
+    #[test]
+    fn it_reports_statements_after_return() {
+        let code = "<?php function foo() { return; echo 1; }";
+
+        assert_eq!(analyse(code), vec!["deadCode.unreachable"]);
+    }
+
+    #[test]
+    fn it_reports_statements_without_expressions() {
+        let code = "<?php while (true) { continue; break; foo(); }";
+
+        assert_eq!(analyse(code), vec!["deadCode.unreachable"]);
+    }
+
+    #[test]
+    fn it_handles_calls_on_properties() {
+        let code = "<?php
+        class Service {
+            public function run() {
+                $this->logger->info('x');
+                if ($this->repository->exists()) {
+                    echo 1;
+                }
+                $this->logger?->info('y');
+            }
+        }";
+
+        assert!(analyse(code).is_empty());
+    }
+}
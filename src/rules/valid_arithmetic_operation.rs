@@ -5,7 +5,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
     shared::types::Type,
 };
@@ -16,11 +19,19 @@ use super::Rule;
 pub struct ValidArithmeticOperationRule;
 
 impl Rule for ValidArithmeticOperationRule {
+    fn name(&self) -> &'static str {
+        "ValidArithmeticOperationRule"
+    }
+
+    fn level(&self) -> u8 {
+        5
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         downcast::<Expression>(node).is_some()
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -34,109 +45,121 @@ impl Rule for ValidArithmeticOperationRule {
                 Type::Error => match operation {
                     ArithmeticOperationExpression::Addition { left, plus, right } => messages
                         .error(
+                            "arithmetic.invalid",
                             format!(
                                 "Arithmetic operation + between {} and {} is invalid",
                                 context.get_type(left, definitions),
                                 context.get_type(right, definitions)
                             ),
-                            plus.line,
+                            MessageSpan::with_length(*plus, 1),
                         ),
                     ArithmeticOperationExpression::Subtraction { left, minus, right } => messages
                         .error(
+                            "arithmetic.invalid",
                             format!(
                                 "Arithmetic operation - between {} and {} is invalid",
                                 context.get_type(left, definitions),
                                 context.get_type(right, definitions)
                             ),
-                            minus.line,
+                            MessageSpan::with_length(*minus, 1),
                         ),
                     ArithmeticOperationExpression::Multiplication {
                         left,
                         asterisk,
                         right,
                     } => messages.error(
+                        "arithmetic.invalid",
                         format!(
                             "Arithmetic operation * between {} and {} is invalid",
                             context.get_type(left, definitions),
                             context.get_type(right, definitions)
                         ),
-                        asterisk.line,
+                        MessageSpan::with_length(*asterisk, 1),
                     ),
                     ArithmeticOperationExpression::Division { left, slash, right } => messages
                         .error(
+                            "arithmetic.invalid",
                             format!(
                                 "Arithmetic operation / between {} and {} is invalid",
                                 context.get_type(left, definitions),
                                 context.get_type(right, definitions)
                             ),
-                            slash.line,
+                            MessageSpan::with_length(*slash, 1),
                         ),
                     ArithmeticOperationExpression::Modulo {
                         left,
                         percent,
                         right,
                     } => messages.error(
+                        "arithmetic.invalid",
                         format!(
                             "Arithmetic operation % between {} and {} is invalid",
                             context.get_type(left, definitions),
                             context.get_type(right, definitions)
                         ),
-                        percent.line,
+                        MessageSpan::with_length(*percent, 1),
                     ),
                     ArithmeticOperationExpression::Exponentiation { left, pow, right } => messages
                         .error(
+                            "arithmetic.invalid",
                             format!(
                                 "Arithmetic operation ** between {} and {} is invalid",
                                 context.get_type(left, definitions),
                                 context.get_type(right, definitions)
                             ),
-                            pow.line,
+                            MessageSpan::with_length(*pow, 2),
                         ),
                     ArithmeticOperationExpression::Negative { minus, right } => messages.error(
+                        "arithmetic.invalid",
                         format!(
                             "Arithmetic operation -{} is invalid",
                             context.get_type(right, definitions)
                         ),
-                        minus.line,
+                        MessageSpan::with_length(*minus, 1),
                     ),
                     ArithmeticOperationExpression::Positive { plus, right } => messages.error(
+                        "arithmetic.invalid",
                         format!(
                             "Arithmetic operation +{} is invalid",
                             context.get_type(right, definitions)
                         ),
-                        plus.line,
+                        MessageSpan::with_length(*plus, 1),
                     ),
                     ArithmeticOperationExpression::PreIncrement { increment, right } => messages
                         .error(
+                            "arithmetic.invalid",
                             format!(
                                 "Arithmetic operation ++{} is invalid",
                                 context.get_type(right, definitions)
                             ),
-                            increment.line,
+                            MessageSpan::with_length(*increment, 2),
                         ),
                     ArithmeticOperationExpression::PostIncrement { left, increment } => messages
                         .error(
+                            "arithmetic.invalid",
                             format!(
                                 "Arithmetic operation {}++ is invalid",
                                 context.get_type(left, definitions)
                             ),
-                            increment.line,
+                            MessageSpan::with_length(*increment, 2),
                         ),
                     ArithmeticOperationExpression::PreDecrement { decrement, right } => messages
                         .error(
+                            "arithmetic.invalid",
                             format!(
                                 "Arithmetic operation --{} is invalid",
                                 context.get_type(right, definitions)
                             ),
-                            decrement.line,
+                            MessageSpan::with_length(*decrement, 2),
                         ),
                     ArithmeticOperationExpression::PostDecrement { left, decrement } => messages
                         .error(
+                            "arithmetic.invalid",
                             format!(
                                 "Arithmetic operation {}-- is invalid",
                                 context.get_type(left, definitions)
                             ),
-                            decrement.line,
+                            MessageSpan::with_length(*decrement, 2),
                         ),
                 },
                 _ => return,
@@ -9,7 +9,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
     shared::types::Type,
 };
@@ -20,11 +23,19 @@ use super::Rule;
 pub struct ValidAssignmentRule;
 
 impl Rule for ValidAssignmentRule {
+    fn name(&self) -> &'static str {
+        "ValidAssignmentRule"
+    }
+
+    fn level(&self) -> u8 {
+        3
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         downcast::<AssignmentOperationExpression>(node).is_some()
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -42,8 +53,10 @@ impl Rule for ValidAssignmentRule {
 
         // 2. Check that the left hand side is a plain variable.
         //    TODO: Add support for assigning to arrays and objects.
-        let variable_name = match assignment_operation_expression.left() {
-            Expression::Variable(Variable::SimpleVariable(SimpleVariable { name, .. })) => name,
+        let (variable_name, span) = match assignment_operation_expression.left() {
+            Expression::Variable(Variable::SimpleVariable(SimpleVariable { name, span })) => {
+                (name, MessageSpan::with_length(*span, name.len()))
+            }
             _ => return,
         };
 
@@ -53,15 +66,10 @@ impl Rule for ValidAssignmentRule {
         // 4. If the type of the right-hand side if `void` (null), we should warn.
         if value_type == Type::Void {
             messages.error(
+                "assign.void",
                 format!("Assignment of void to variable {variable_name}"),
-                assignment_operation_expression.operator().line,
+                span,
             );
         }
-
-        // 4. Enter the new variable type into the context.
-        context.set_variable(variable_name.clone(), value_type);
-
-        // 5. Assert the variable is in the context (it should be).
-        debug_assert!(context.has_variable(variable_name));
     }
 }
@@ -9,7 +9,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
     rules::Rule,
 };
@@ -18,11 +21,15 @@ use crate::{
 pub struct ValidClassRule;
 
 impl Rule for ValidClassRule {
+    fn name(&self) -> &'static str {
+        "ValidClassRule"
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         downcast::<NewExpression>(node).is_some()
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -35,7 +42,10 @@ impl Rule for ValidClassRule {
             Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
                 value: class_name,
                 span,
-            })) => (class_name, span),
+            })) => (
+                class_name,
+                MessageSpan::with_length(*span, class_name.len()),
+            ),
             _ => return,
         };
 
@@ -44,6 +54,7 @@ impl Rule for ValidClassRule {
         if definition.is_none() {
             // TODO: Add a check for execution inside of a `class_exists` call.
             messages.error(
+                "class.notFound",
                 format!(
                     "Class `{}` (DBG: {}, {}) not found",
                     name,
@@ -55,7 +66,7 @@ impl Rule for ValidClassRule {
                         global_name
                     }
                 ),
-                span.line,
+                span,
             );
 
             return;
@@ -65,8 +76,9 @@ impl Rule for ValidClassRule {
 
         if definition.is_abstract() {
             messages.error(
+                "new.abstract",
                 format!("Cannot instantiate abstract class `{name}`"),
-                span.line,
+                span,
             );
         }
     }
@@ -10,7 +10,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
     rules::Rule,
 };
@@ -19,11 +22,15 @@ use crate::{
 pub struct ValidFunctionRule;
 
 impl Rule for ValidFunctionRule {
+    fn name(&self) -> &'static str {
+        "ValidFunctionRule"
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         downcast::<FunctionCallExpression>(node).is_some()
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -40,11 +47,16 @@ impl Rule for ValidFunctionRule {
             _ => return,
         };
 
+        let call_span =
+            MessageSpan::between(*span, function_call_expression.arguments.right_parenthesis);
+        let span = MessageSpan::with_length(*span, function_name.len());
+
         let definition = definitions.get_function(function_name, context);
 
         if definition.is_none() {
             // TODO: Add a check for execution inside of a `function_exists` call.
             messages.error(
+                "function.notFound",
                 format!(
                     "Function `{}` (DBG: {}, {}) not found",
                     function_name,
@@ -56,7 +68,7 @@ impl Rule for ValidFunctionRule {
                         global_name
                     }
                 ),
-                span.line,
+                span,
             );
 
             return;
@@ -68,26 +80,28 @@ impl Rule for ValidFunctionRule {
 
         if function_call_expression.arguments.arguments.len() < min_arity {
             messages.error(
+                "arguments.count",
                 format!(
                     "Function {}() requires {} arguments, {} given",
                     function_name,
                     min_arity,
                     function_call_expression.arguments.arguments.len()
                 ),
-                span.line,
+                call_span,
             );
             return;
         }
 
         if function_call_expression.arguments.arguments.len() > max_arity {
             messages.error(
+                "arguments.count",
                 format!(
                     "Function {}() requires {} arguments, {} given",
                     function_name,
                     max_arity,
                     function_call_expression.arguments.arguments.len()
                 ),
-                span.line,
+                call_span,
             );
             return;
         }
@@ -103,8 +117,9 @@ impl Rule for ValidFunctionRule {
                 }) => {
                     if has_encountered_named_argument {
                         messages.error(
+                            "argument.positionalAfterNamed",
                             "Positional argument cannot follow named argument",
-                            span.line,
+                            span,
                         );
                         continue;
                     }
@@ -123,7 +138,7 @@ impl Rule for ValidFunctionRule {
 
                     if !parameter_type.compatible(&argument_type) {
                         // Doesn't make sense to zero-index the position, so we add 1.
-                        messages.error(format!("Argument {} of type {} is not compatible with parameter {} of type {}", position + 1, argument_type, parameter.name, parameter_type), span.line);
+                        messages.error("argument.type", format!("Argument {} of type {} is not compatible with parameter {} of type {}", position + 1, argument_type, parameter.name, parameter_type), span);
                     }
                 }
                 Argument::Named(NamedArgument {
@@ -144,12 +159,12 @@ impl Rule for ValidFunctionRule {
                                 if p.spread {
                                     parameter = Some(p);
                                 } else {
-                                    messages.error(format!("Function {function_name}() does not have a parameter named {name}"), span.line);
+                                    messages.error("argument.unknown", format!("Function {function_name}() does not have a parameter named {name}"), MessageSpan::with_length(name.span, name.value.len()));
                                     continue;
                                 }
                             }
                             None => {
-                                messages.error(format!("Function {function_name}() does not have a parameter named {name}"), span.line);
+                                messages.error("argument.unknown", format!("Function {function_name}() does not have a parameter named {name}"), MessageSpan::with_length(name.span, name.value.len()));
                                 continue;
                             }
                         }
@@ -166,7 +181,7 @@ impl Rule for ValidFunctionRule {
                     let argument_type = context.get_type(value, definitions);
 
                     if !parameter_type.compatible(&argument_type) {
-                        messages.error(format!("Argument {} of type {} is not compatible with parameter {} of type {}", name, argument_type, parameter.name, parameter_type), span.line);
+                        messages.error("argument.type", format!("Argument {} of type {} is not compatible with parameter {} of type {}", name, argument_type, parameter.name, parameter_type), span);
                     }
                 }
             }
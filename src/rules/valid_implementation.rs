@@ -0,0 +1,181 @@
+use pxp_parser::{
+    downcast::downcast, lexer::byte_string::ByteString, node::Node,
+    parser::ast::classes::ClassStatement,
+};
+
+use crate::{
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
+    definitions::{
+        classes::ClassDefinition, collection::DefinitionCollection, functions::MethodDefinition,
+    },
+};
+
+use super::Rule;
+
+/// Checks that concrete classes define every method of the interfaces that they
+/// implement, and every abstract method that they inherit from their parents.
+#[derive(Debug)]
+pub struct ValidImplementationRule;
+
+impl Rule for ValidImplementationRule {
+    fn name(&self) -> &'static str {
+        "ValidImplementationRule"
+    }
+
+    fn should_run(&self, node: &dyn Node) -> bool {
+        downcast::<ClassStatement>(node).is_some()
+    }
+
+    fn enter_node(
+        &mut self,
+        node: &mut dyn Node,
+        definitions: &DefinitionCollection,
+        messages: &mut MessageCollector,
+        context: &mut Context,
+    ) {
+        let class_statement = downcast::<ClassStatement>(node).unwrap();
+        let class_name = context.resolve_name(&class_statement.name.value);
+
+        let class = match definitions.get_class(&class_name, context) {
+            Some(class) => class,
+            None => return,
+        };
+
+        if class.is_abstract() {
+            return;
+        }
+
+        let span =
+            MessageSpan::with_length(class_statement.name.span, class_statement.name.value.len());
+
+        // The class and each of its parents, starting with the class itself.
+        let mut ancestors = vec![class];
+        while let Some(parent) = ancestors
+            .last()
+            .and_then(|class| class.extends.as_ref())
+            .and_then(|parent| definitions.get_class(parent, context))
+        {
+            // Guard against classes that (indirectly) extend themselves.
+            if ancestors.iter().any(|class| class.name == parent.name) {
+                break;
+            }
+
+            ancestors.push(parent);
+        }
+
+        // The lowercase names of the methods that were reported, since method names
+        // are case-insensitive.
+        let mut reported: Vec<Vec<u8>> = Vec::new();
+
+        for ancestor in ancestors.iter() {
+            for interface in ancestor.implements.iter() {
+                let interface = match definitions.get_interface(interface, context) {
+                    Some(interface) => interface,
+                    None => continue,
+                };
+
+                for (interface_name, method) in interface.get_all_methods(definitions, context) {
+                    if reported.contains(&method.name.to_ascii_lowercase())
+                        || is_implemented(&ancestors, &method.name, definitions, context)
+                    {
+                        continue;
+                    }
+
+                    reported.push(method.name.to_ascii_lowercase());
+                    messages.error(
+                        "method.notImplemented",
+                        format!(
+                            "Class {} must implement method {}::{}()",
+                            class.name, interface_name, method.name
+                        ),
+                        span,
+                    );
+                }
+            }
+        }
+
+        for ancestor in ancestors.iter().skip(1) {
+            for method in ancestor.methods.iter().filter(|m| m.is_abstract()) {
+                if reported.contains(&method.name.to_ascii_lowercase())
+                    || is_implemented(&ancestors, &method.name, definitions, context)
+                {
+                    continue;
+                }
+
+                reported.push(method.name.to_ascii_lowercase());
+                messages.error(
+                    "method.notImplemented",
+                    format!(
+                        "Class {} must implement abstract method {}::{}()",
+                        class.name, ancestor.name, method.name
+                    ),
+                    span,
+                );
+            }
+        }
+    }
+}
+
+/// Whether the class or one of its parents, given as the class followed by its
+/// ancestors, defines or uses a trait with a concrete method of the given name. An
+/// abstract declaration of the method doesn't stop the others from being checked.
+fn is_implemented(
+    ancestors: &[&ClassDefinition],
+    name: &ByteString,
+    definitions: &DefinitionCollection,
+    context: &Context,
+) -> bool {
+    ancestors.iter().any(|class| {
+        has_concrete_method(
+            &class.methods,
+            &class.uses,
+            name,
+            definitions,
+            context,
+            &mut Vec::new(),
+        )
+    })
+}
+
+/// Whether the given methods, or the methods of the given traits, include a concrete
+/// method of the given name.
+fn has_concrete_method<'a>(
+    methods: &[MethodDefinition],
+    uses: &'a [ByteString],
+    name: &ByteString,
+    definitions: &'a DefinitionCollection,
+    context: &Context,
+    visited: &mut Vec<&'a ByteString>,
+) -> bool {
+    if methods
+        .iter()
+        .any(|method| method.name.eq_ignore_ascii_case(name) && !method.is_abstract())
+    {
+        return true;
+    }
+
+    uses.iter().any(|trait_| {
+        // Guard against traits that (indirectly) use themselves.
+        if visited.contains(&trait_) {
+            return false;
+        }
+
+        visited.push(trait_);
+
+        definitions
+            .get_trait(trait_, context)
+            .is_some_and(|trait_| {
+                has_concrete_method(
+                    &trait_.methods,
+                    &trait_.uses,
+                    name,
+                    definitions,
+                    context,
+                    visited,
+                )
+            })
+    })
+}
@@ -10,7 +10,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
 };
 
@@ -20,11 +23,15 @@ use super::Rule;
 pub struct ValidStaticCallRule;
 
 impl Rule for ValidStaticCallRule {
+    fn name(&self) -> &'static str {
+        "ValidStaticCallRule"
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         downcast::<StaticMethodCallExpression>(node).is_some()
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -34,11 +41,18 @@ impl Rule for ValidStaticCallRule {
         let static_method_call = downcast::<StaticMethodCallExpression>(node).unwrap();
 
         // 1. Check that the method is a simple identifier, i.e. Foo::bar().
-        let method_name = match &static_method_call.method {
-            Identifier::SimpleIdentifier(SimpleIdentifier { value, .. }) => value,
+        let (method_name, name_span) = match &static_method_call.method {
+            Identifier::SimpleIdentifier(SimpleIdentifier { value, span }) => {
+                (value, MessageSpan::with_length(*span, value.len()))
+            }
             _ => return,
         };
 
+        let span = MessageSpan::between(
+            static_method_call.double_colon,
+            static_method_call.arguments.right_parenthesis,
+        );
+
         // 2. Get the class name based on the left-hand side of the call.
         //    If the method call is on `self`, then the class name is pulled from the context.
         //    If the method call is on `static`, then the class name is pulled from the context.
@@ -51,8 +65,9 @@ impl Rule for ValidStaticCallRule {
             Expression::Self_ => {
                 if !context.is_in_class() {
                     messages.error(
+                        "staticMethod.outsideClass",
                         format!("Calling self::{method_name}() outside of class context"),
-                        static_method_call.double_colon.line,
+                        span,
                     );
                     return;
                 }
@@ -62,8 +77,9 @@ impl Rule for ValidStaticCallRule {
             Expression::Static => {
                 if !context.is_in_class() {
                     messages.error(
+                        "staticMethod.outsideClass",
                         format!("Calling static::{method_name}() outside of class context"),
-                        static_method_call.double_colon.line,
+                        span,
                     );
                     return;
                 }
@@ -73,8 +89,9 @@ impl Rule for ValidStaticCallRule {
             Expression::Parent => {
                 if !context.is_in_class() {
                     messages.error(
+                        "staticMethod.outsideClass",
                         format!("Calling parent::{method_name}() outside of class context"),
-                        static_method_call.double_colon.line,
+                        span,
                     );
                     return;
                 }
@@ -85,12 +102,13 @@ impl Rule for ValidStaticCallRule {
 
                 if child_class.extends.is_none() {
                     messages.error(
+                        "staticMethod.noParent",
                         format!(
                             "Calling parent::{}() but {} does not extend any class",
                             method_name,
                             context.classish_context()
                         ),
-                        static_method_call.double_colon.line,
+                        span,
                     );
                     return;
                 }
@@ -105,8 +123,9 @@ impl Rule for ValidStaticCallRule {
             Some(class) => class,
             None => {
                 messages.error(
+                    "class.notFound",
                     format!("Call to {class_name}::{method_name}() on unknown class {class_name}"),
-                    static_method_call.double_colon.line,
+                    span,
                 );
                 return;
             }
@@ -135,8 +154,9 @@ impl Rule for ValidStaticCallRule {
             } else if !has_call_static {
                 // TODO: Check if class's docblock has an @method.
                 messages.error(
+                    "staticMethod.notFound",
                     format!("Call to undefined method {class_name}::{method_name}()"),
-                    static_method_call.double_colon.line,
+                    name_span,
                 );
                 return;
             }
@@ -152,8 +172,9 @@ impl Rule for ValidStaticCallRule {
         // 6. Check that the method is static.
         if !method.is_static() {
             messages.error(
+                "staticMethod.nonStatic",
                 format!("Call to non-static method {class_name}::{method_name}()"),
-                static_method_call.double_colon.line,
+                span,
             );
             return;
         }
@@ -161,8 +182,9 @@ impl Rule for ValidStaticCallRule {
         // 7. Check that the method is not abstract.
         if method.is_abstract() && !class.is_abstract() {
             messages.error(
+                "staticMethod.abstract",
                 format!("Call to abstract method {class_name}::{method_name}()"),
-                static_method_call.double_colon.line,
+                span,
             );
             return;
         }
@@ -172,6 +194,11 @@ impl Rule for ValidStaticCallRule {
             // If we're not in a class context, then calling a static protected or private method isn't allowed at all.
             if !context.is_in_class() {
                 messages.error(
+                    if method.is_protected() {
+                        "staticMethod.protected"
+                    } else {
+                        "staticMethod.private"
+                    },
                     format!(
                         "Call to {} method {}::{}()",
                         if method.is_protected() {
@@ -184,7 +211,7 @@ impl Rule for ValidStaticCallRule {
                         class_name,
                         method_name
                     ),
-                    static_method_call.double_colon.line,
+                    span,
                 );
                 return;
             }
@@ -196,8 +223,9 @@ impl Rule for ValidStaticCallRule {
             // If we're not in the same class, or if the method is inherited, then calling a private method is disallowed.
             if current_class != class && has_inherited && method.is_private() {
                 messages.error(
+                    "staticMethod.private",
                     format!("Call to private method {class_name}::{method_name}()"),
-                    static_method_call.double_colon.line,
+                    span,
                 );
                 return;
             }
@@ -205,38 +233,40 @@ impl Rule for ValidStaticCallRule {
             // If the method is protected, then we need to check if the current class inherits the method from a class in the inheritance chain.
             if method.is_protected() && !has_inherited && current_class != class {
                 messages.error(
+                    "staticMethod.protected",
                     format!("Call to protected method {class_name}::{method_name}()"),
-                    static_method_call.double_colon.line,
+                    span,
                 );
                 return;
             }
         }
 
-        let span = static_method_call.double_colon;
         let min_arity = method.min_arity();
         let max_arity = method.max_arity();
 
         if static_method_call.arguments.arguments.len() < min_arity {
             messages.error(
+                "arguments.count",
                 format!(
                     "Method {class_name}::{method_name}() requires {} arguments, {} given",
                     min_arity,
                     static_method_call.arguments.arguments.len()
                 ),
-                span.line,
+                span,
             );
             return;
         }
 
         if static_method_call.arguments.arguments.len() > max_arity {
             messages.error(
+                "arguments.count",
                 format!(
                     "Method {class_name}::{}() requires {} arguments, {} given",
                     method_name,
                     max_arity,
                     static_method_call.arguments.arguments.len()
                 ),
-                span.line,
+                span,
             );
             return;
         }
@@ -252,8 +282,9 @@ impl Rule for ValidStaticCallRule {
                 }) => {
                     if has_encountered_named_argument {
                         messages.error(
+                            "argument.positionalAfterNamed",
                             "Positional argument cannot follow named argument",
-                            span.line,
+                            span,
                         );
                         continue;
                     }
@@ -272,7 +303,7 @@ impl Rule for ValidStaticCallRule {
 
                     if !parameter_type.compatible(&argument_type) {
                         // Doesn't make sense to zero-index the position, so we add 1.
-                        messages.error(format!("Argument {} of type {} is not compatible with parameter {} of type {}", position + 1, argument_type, parameter.name, parameter_type), span.line);
+                        messages.error("argument.type", format!("Argument {} of type {} is not compatible with parameter {} of type {}", position + 1, argument_type, parameter.name, parameter_type), span);
                     }
                 }
                 Argument::Named(NamedArgument {
@@ -293,12 +324,12 @@ impl Rule for ValidStaticCallRule {
                                 if p.spread {
                                     parameter = Some(p);
                                 } else {
-                                    messages.error(format!("Method $this->{method_name}() does not have a parameter named {name}"), span.line);
+                                    messages.error("argument.unknown", format!("Method $this->{method_name}() does not have a parameter named {name}"), MessageSpan::with_length(name.span, name.value.len()));
                                     continue;
                                 }
                             }
                             None => {
-                                messages.error(format!("Method $this->{method_name}() does not have a parameter named {name}"), span.line);
+                                messages.error("argument.unknown", format!("Method $this->{method_name}() does not have a parameter named {name}"), MessageSpan::with_length(name.span, name.value.len()));
                                 continue;
                             }
                         }
@@ -315,7 +346,7 @@ impl Rule for ValidStaticCallRule {
                     let argument_type = context.get_type(value, definitions);
 
                     if !parameter_type.compatible(&argument_type) {
-                        messages.error(format!("Argument {} of type {} is not compatible with parameter {} of type {}", name, argument_type, parameter.name, parameter_type), span.line);
+                        messages.error("argument.type", format!("Argument {} of type {} is not compatible with parameter {} of type {}", name, argument_type, parameter.name, parameter_type), span);
                     }
                 }
             }
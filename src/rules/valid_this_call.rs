@@ -11,7 +11,10 @@ use pxp_parser::{
 };
 
 use crate::{
-    analyser::{context::Context, messages::MessageCollector},
+    analyser::{
+        context::Context,
+        messages::{MessageCollector, MessageSpan},
+    },
     definitions::collection::DefinitionCollection,
 };
 
@@ -21,11 +24,15 @@ use super::Rule;
 pub struct ValidThisCallRule;
 
 impl Rule for ValidThisCallRule {
+    fn name(&self) -> &'static str {
+        "ValidThisCallRule"
+    }
+
     fn should_run(&self, node: &dyn Node) -> bool {
         downcast::<MethodCallExpression>(node).is_some()
     }
 
-    fn run(
+    fn enter_node(
         &mut self,
         node: &mut dyn Node,
         definitions: &DefinitionCollection,
@@ -35,30 +42,38 @@ impl Rule for ValidThisCallRule {
         let method_call_expression = downcast::<MethodCallExpression>(node).unwrap();
 
         // 1. Check that the method call is on $this.
-        match method_call_expression.target.as_ref() {
-            Expression::Variable(Variable::SimpleVariable(SimpleVariable { name, .. })) => {
+        let this_span = match method_call_expression.target.as_ref() {
+            Expression::Variable(Variable::SimpleVariable(SimpleVariable { name, span })) => {
                 if name != &ByteString::from(b"$this") {
                     return;
                 }
+
+                *span
             }
             _ => return,
-        }
+        };
 
         // 2. Check that the method name is not variable.
-        let method_name = match method_call_expression.method.as_ref() {
+        let (method_name, name_span) = match method_call_expression.method.as_ref() {
             Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
                 value,
-                ..
-            })) => value,
+                span,
+            })) => (value, MessageSpan::with_length(*span, value.len())),
             _ => return,
         };
 
+        let span = MessageSpan::between(
+            this_span,
+            method_call_expression.arguments.right_parenthesis,
+        );
+
         // 3. Check if currently inside of a classish context.
         // TODO: We should also calling $this->foo() inside of a Closure since it could be bound to an object.
         if !context.is_in_class() {
             messages.error(
+                "method.thisOutsideClass",
                 format!("Calling $this->{method_name}() outside of class context"),
-                method_call_expression.arrow.line,
+                span,
             );
             return;
         }
@@ -89,10 +104,11 @@ impl Rule for ValidThisCallRule {
             } else if !has_call_magic {
                 // TODO: Check if class's docblock has an @method.
                 messages.error(
+                    "method.notFound",
                     format!(
                         "Call to undefined method $this->{method_name}() on {classish_context}"
                     ),
-                    method_call_expression.arrow.line,
+                    name_span,
                 );
                 return;
             }
@@ -114,39 +130,41 @@ impl Rule for ValidThisCallRule {
             //     calling a private or protected method is perfectly fine.
             if class_definition != method_class_context && method.is_private() {
                 messages.error(
+                    "method.private",
                     format!("Call to private method $this->{method_name}()"),
-                    method_call_expression.arrow.line,
+                    name_span,
                 );
                 return;
             }
         }
 
-        let span = method_call_expression.arrow;
         let min_arity = method.min_arity();
         let max_arity = method.max_arity();
 
         if method_call_expression.arguments.arguments.len() < min_arity {
             messages.error(
+                "arguments.count",
                 format!(
                     "Method $this->{}() requires {} arguments, {} given",
                     method_name,
                     min_arity,
                     method_call_expression.arguments.arguments.len()
                 ),
-                span.line,
+                span,
             );
             return;
         }
 
         if method_call_expression.arguments.arguments.len() > max_arity {
             messages.error(
+                "arguments.count",
                 format!(
                     "Method $this->{}() requires {} arguments, {} given",
                     method_name,
                     max_arity,
                     method_call_expression.arguments.arguments.len()
                 ),
-                span.line,
+                span,
             );
             return;
         }
@@ -162,8 +180,9 @@ impl Rule for ValidThisCallRule {
                 }) => {
                     if has_encountered_named_argument {
                         messages.error(
+                            "argument.positionalAfterNamed",
                             "Positional argument cannot follow named argument",
-                            span.line,
+                            span,
                         );
                         continue;
                     }
@@ -182,7 +201,7 @@ impl Rule for ValidThisCallRule {
 
                     if !parameter_type.compatible(&argument_type) {
                         // Doesn't make sense to zero-index the position, so we add 1.
-                        messages.error(format!("Argument {} of type {} is not compatible with parameter {} of type {}", position + 1, argument_type, parameter.name, parameter_type), span.line);
+                        messages.error("argument.type", format!("Argument {} of type {} is not compatible with parameter {} of type {}", position + 1, argument_type, parameter.name, parameter_type), span);
                     }
                 }
                 Argument::Named(NamedArgument {
@@ -203,12 +222,12 @@ impl Rule for ValidThisCallRule {
                                 if p.spread {
                                     parameter = Some(p);
                                 } else {
-                                    messages.error(format!("Method $this->{method_name}() does not have a parameter named {name}"), span.line);
+                                    messages.error("argument.unknown", format!("Method $this->{method_name}() does not have a parameter named {name}"), MessageSpan::with_length(name.span, name.value.len()));
                                     continue;
                                 }
                             }
                             None => {
-                                messages.error(format!("Method $this->{method_name}() does not have a parameter named {name}"), span.line);
+                                messages.error("argument.unknown", format!("Method $this->{method_name}() does not have a parameter named {name}"), MessageSpan::with_length(name.span, name.value.len()));
                                 continue;
                             }
                         }
@@ -225,7 +244,7 @@ impl Rule for ValidThisCallRule {
                     let argument_type = context.get_type(value, definitions);
 
                     if !parameter_type.compatible(&argument_type) {
-                        messages.error(format!("Argument {} of type {} is not compatible with parameter {} of type {}", name, argument_type, parameter.name, parameter_type), span.line);
+                        messages.error("argument.type", format!("Argument {} of type {} is not compatible with parameter {} of type {}", name, argument_type, parameter.name, parameter_type), span);
                     }
                 }
             }
@@ -0,0 +1,394 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    analyser::{
+        collected::{CollectedData, ProjectMessages},
+        extensions::DynamicReturnTypeExtension,
+        messages::{MessageCollector, MessageSeverity},
+        Analyser,
+    },
+    baseline::{Baseline, BaselineEntry},
+    composer::ComposerProject,
+    config::Configuration,
+    definitions::collection::DefinitionCollection,
+    project::{
+        discover, normalise,
+        patterns::{PathFilter, PathPattern},
+        Project,
+    },
+    rules::{self, ProjectRule, Rule},
+    stubs::PhpVersion,
+};
+
+type RuleFactory = Box<dyn Fn(&Configuration) -> Vec<Box<dyn Rule>> + Send + Sync>;
+
+/// Runs the analysis for a project with the built-in rules, plus any rules and
+/// extensions that are added to it. This is what the `statan` binary uses, so
+/// custom binaries can register their own rules and behave in exactly the same way.
+///
+/// ```ignore
+/// let configuration = Configuration::load(Path::new("statan.toml"))?;
+///
+/// let mut runner = Runner::new(configuration);
+/// runner.add_rule(|| Box::new(NoGlobalsRule));
+/// runner.add_extension(ContainerExtension);
+///
+/// let report = runner.run()?;
+/// ```
+pub struct Runner {
+    configuration: Configuration,
+    php_version: Option<PhpVersion>,
+    rules: Vec<RuleFactory>,
+    project_rules: Vec<Box<dyn ProjectRule>>,
+    extensions: Vec<Arc<dyn DynamicReturnTypeExtension>>,
+    verbose: bool,
+}
+
+/// The files and definitions of a project, ready to be analysed.
+#[derive(Debug)]
+pub struct LoadedProject {
+    pub project: Project,
+    pub definitions: DefinitionCollection,
+    /// Problems that didn't stop the project from loading, such as an invalid
+    /// `composer.json` file.
+    pub warnings: Vec<String>,
+}
+
+/// The results of analysing a project.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// The messages for each analysed file, in path order.
+    pub messages: Vec<MessageCollector>,
+    /// The baseline entries that no longer occur as many times as they used to.
+    pub stale_baseline_entries: Vec<BaselineEntry>,
+    /// Problems that didn't stop the project from being analysed, such as an invalid
+    /// `composer.json` file.
+    pub warnings: Vec<String>,
+}
+
+impl Report {
+    pub fn count(&self, severity: MessageSeverity) -> usize {
+        self.messages
+            .iter()
+            .flat_map(|messages| messages.iter())
+            .filter(|message| message.severity == severity)
+            .count()
+    }
+
+    /// The number of files with a message of at least the given severity.
+    pub fn failing_files(&self, severity: MessageSeverity) -> usize {
+        self.messages
+            .iter()
+            .filter(|messages| {
+                messages
+                    .iter()
+                    .any(|message| message.severity.is_at_least(severity))
+            })
+            .count()
+    }
+}
+
+impl Runner {
+    /// Create a runner with the built-in rules.
+    pub fn new(configuration: Configuration) -> Self {
+        let built_in: RuleFactory = Box::new(rules::all);
+
+        Self {
+            php_version: configuration.php_version,
+            project_rules: rules::project(&configuration),
+            configuration,
+            rules: vec![built_in],
+            extensions: Vec::new(),
+            verbose: false,
+        }
+    }
+
+    pub fn configuration(&self) -> &Configuration {
+        &self.configuration
+    }
+
+    /// Load the bundled stubs for the given PHP version instead of the configured one.
+    pub fn set_php_version(&mut self, php_version: PhpVersion) {
+        self.php_version = Some(php_version);
+    }
+
+    /// Include a backtrace in the messages reported for internal errors.
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
+    /// Register a rule. Files are analysed in parallel with an analyser per thread,
+    /// so the rule is created by a function that's called once for each analyser.
+    ///
+    /// Custom rules are enabled, disabled and configured by name, just like the
+    /// built-in ones.
+    pub fn add_rule(&mut self, rule: impl Fn() -> Box<dyn Rule> + Send + Sync + 'static) {
+        self.rules
+            .push(Box::new(move |_: &Configuration| vec![rule()]));
+    }
+
+    /// Register a project rule, which runs once every file has been analysed.
+    pub fn add_project_rule(&mut self, rule: impl ProjectRule + 'static) {
+        self.project_rules.push(Box::new(rule));
+    }
+
+    /// Register an extension that computes the return types of calls.
+    pub fn add_extension(&mut self, extension: impl DynamicReturnTypeExtension + 'static) {
+        self.extensions.push(Arc::new(extension));
+    }
+
+    /// The rules that are enabled by the configuration. An error is returned if the
+    /// configuration refers to a rule that doesn't exist.
+    pub fn rules(&self) -> Result<Vec<Box<dyn Rule>>, String> {
+        let rules = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule(&self.configuration))
+            .collect::<Vec<Box<dyn Rule>>>();
+
+        let registered = rules
+            .iter()
+            .map(|rule| (rule.name(), rule.level()))
+            .chain(
+                self.project_rules
+                    .iter()
+                    .map(|rule| (rule.name(), rule.level())),
+            )
+            .collect::<Vec<(&str, u8)>>();
+        self.configuration.validate_rules(&registered)?;
+
+        Ok(rules
+            .into_iter()
+            .filter(|rule| {
+                self.configuration
+                    .is_rule_enabled(rule.name(), rule.level())
+            })
+            .collect())
+    }
+
+    /// Create the filter for the files to analyse. The configured paths are used if
+    /// no patterns are given, and the configured excludes are always applied.
+    pub fn path_filter(
+        &self,
+        includes: Vec<PathPattern>,
+        excludes: Vec<PathPattern>,
+    ) -> Result<PathFilter, String> {
+        let includes = if includes.is_empty() {
+            self.configuration
+                .paths
+                .iter()
+                .map(|path| PathPattern::new(&path.to_string_lossy()))
+                .collect::<Result<Vec<PathPattern>, String>>()?
+        } else {
+            includes
+        };
+
+        if includes.is_empty() {
+            return Err(
+                "No paths to analyse, pass a path or add some to the paths list in statan.toml."
+                    .to_string(),
+            );
+        }
+
+        let excludes = excludes
+            .into_iter()
+            .chain(
+                self.configuration
+                    .excludes
+                    .iter()
+                    .cloned()
+                    .map(PathPattern::Path),
+            )
+            .collect();
+
+        Ok(PathFilter::new(
+            self.configuration.root(),
+            includes,
+            excludes,
+        ))
+    }
+
+    /// Discover and parse the project's files, then collect the definitions from them,
+    /// the bundled stubs and any Composer packages that they reference.
+    pub fn load_project(&self, analysed_paths: &[PathBuf]) -> Result<LoadedProject, String> {
+        let mut warnings = Vec::new();
+
+        let root = self.configuration.root();
+        let composer = ComposerProject::load(root).unwrap_or_else(|error| {
+            warnings.push(format!("Ignoring Composer configuration, {error}"));
+            None
+        });
+
+        // If the project uses Composer, we only need to scan the autoload paths for
+        // definitions. Otherwise we have no choice but to scan everything.
+        let extensions = self.configuration.extensions();
+        let mut files = match &composer {
+            Some(composer) => discover(&composer.definition_paths(), &extensions)?,
+            None => discover(&[root.to_path_buf()], &extensions)?,
+        };
+
+        files.extend(discover(&self.configuration.scan_paths, &extensions)?);
+        files.extend(discover(&self.configuration.stubs, &extensions)?);
+        files.extend(discover(analysed_paths, &extensions)?);
+        files.retain(|file| !self.configuration.is_excluded(&normalise(file)));
+
+        let mut project = Project::load(files)
+            .map_err(|error| format!("failed to read project files: {error}"))?;
+
+        let mut definitions = project.collect_definitions();
+
+        // The bundled stubs are merged in after the project's own definitions so that
+        // polyfills and other user-land definitions take precedence.
+        definitions.merge(self.php_version.unwrap_or_default().definitions());
+
+        // Classes from installed packages are only loaded when the project references them.
+        if let Some(composer) = &composer {
+            project.resolve_missing_definitions(&mut definitions, |name| {
+                composer.find_class_file(name)
+            });
+        }
+
+        Ok(LoadedProject {
+            project,
+            definitions,
+            warnings,
+        })
+    }
+
+    /// Create an analyser with the enabled rules and the registered extensions.
+    pub fn create_analyser(&self, definitions: Arc<DefinitionCollection>) -> Analyser {
+        let mut analyser = Analyser::new(definitions);
+
+        // The rules are validated before any file is analysed, so this can't fail.
+        for rule in self.rules().unwrap() {
+            analyser.add_rule(rule);
+        }
+
+        for extension in self.extensions.iter() {
+            analyser.add_extension(extension.clone());
+        }
+
+        for (rule, severity) in self.configuration.rules.severity.iter() {
+            analyser.set_severity(rule, *severity);
+        }
+
+        analyser.set_report_unmatched_suppressions(self.configuration.report_unmatched_ignores());
+        analyser.set_verbose(self.verbose);
+
+        analyser
+    }
+
+    /// Run the enabled project rules with the data collected while analysing the files.
+    /// Their messages are added to the files they're about, as long as those files
+    /// were analysed.
+    pub fn run_project_rules(
+        &self,
+        definitions: &DefinitionCollection,
+        message_collections: &mut [MessageCollector],
+    ) {
+        let mut reported = Vec::new();
+        let data = CollectedData::new(message_collections);
+
+        for rule in self.project_rules.iter() {
+            if !self
+                .configuration
+                .is_rule_enabled(rule.name(), rule.level())
+            {
+                continue;
+            }
+
+            let mut messages = ProjectMessages::new();
+            rule.run(&data, definitions, &mut messages);
+
+            for mut collection in messages.into_collections() {
+                if let Some(severity) = self.configuration.rules.severity.get(rule.name()) {
+                    collection.set_severity_since(0, *severity);
+                }
+
+                reported.push(collection);
+            }
+        }
+
+        for collection in reported {
+            if let Some(messages) = message_collections
+                .iter_mut()
+                .find(|messages| messages.get_file() == collection.get_file())
+            {
+                messages.merge(collection);
+            }
+        }
+    }
+
+    /// Load the configured baseline. An explicitly configured baseline has to exist,
+    /// but the default one is optional.
+    pub fn load_baseline(&self) -> Result<Option<Baseline>, String> {
+        let path = self.configuration.baseline();
+
+        if self.configuration.baseline.is_some() || path.is_file() {
+            Baseline::load(&path).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Remove the messages that are ignored in the configuration or that are in the
+    /// baseline. The baseline entries that no longer occur are returned.
+    pub fn filter_messages(
+        &self,
+        baseline: Option<&Baseline>,
+        message_collections: &mut [MessageCollector],
+    ) -> Vec<BaselineEntry> {
+        for messages in message_collections.iter_mut() {
+            let file = PathBuf::from(messages.get_file());
+            messages.retain(|message| !self.configuration.is_ignored(&file, message));
+        }
+
+        match baseline {
+            Some(baseline) => baseline.apply(message_collections),
+            None => Vec::new(),
+        }
+    }
+
+    /// Analyse the configured paths, leaving out the messages that are ignored or in
+    /// the baseline.
+    pub fn run(&self) -> Result<Report, String> {
+        let filter = self.path_filter(Vec::new(), Vec::new())?;
+        self.run_filtered(|path| filter.matches(path), &filter.base_paths())
+    }
+
+    /// Analyse the files that match the given predicate, which are found in the given
+    /// paths. Definitions are still collected from the whole project.
+    pub fn run_filtered(
+        &self,
+        is_analysed: impl Fn(&Path) -> bool + Sync + Send,
+        analysed_paths: &[PathBuf],
+    ) -> Result<Report, String> {
+        self.rules()?;
+        let baseline = self.load_baseline()?;
+
+        let LoadedProject {
+            mut project,
+            definitions,
+            warnings,
+        } = self.load_project(analysed_paths)?;
+        project.retain_asts(&is_analysed);
+
+        let definitions = Arc::new(definitions);
+        let mut messages = project.analyse(
+            is_analysed,
+            || self.create_analyser(definitions.clone()),
+            |_| {},
+        );
+        self.run_project_rules(&definitions, &mut messages);
+        let stale_baseline_entries = self.filter_messages(baseline.as_ref(), &mut messages);
+
+        Ok(Report {
+            messages,
+            stale_baseline_entries,
+            warnings,
+        })
+    }
+}
@@ -66,6 +66,18 @@ impl From<ParsedType> for Type {
 }
 
 impl Type {
+    /// Get the names of the classes, interfaces and enums referenced by this type.
+    pub fn referenced_names(&self) -> Vec<&ByteString> {
+        match self {
+            Type::Named(name) => vec![name],
+            Type::Nullable(ty) => ty.referenced_names(),
+            Type::Union(tys) | Type::Intersection(tys) => {
+                tys.iter().flat_map(|ty| ty.referenced_names()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
     pub fn compatible(&self, other: &Type) -> bool {
         if other == &Type::Mixed {
             return true;
@@ -0,0 +1,65 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::definitions::collection::DefinitionCollection;
+
+/// The PHP versions that Statan ships stubs for. The stubs for a given version
+/// include everything that was added in the versions before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum PhpVersion {
+    Php80,
+    Php81,
+    Php82,
+    #[default]
+    Php83,
+}
+
+impl PhpVersion {
+    pub fn definitions(&self) -> DefinitionCollection {
+        let stubs = match self {
+            PhpVersion::Php80 => include_str!(concat!(env!("OUT_DIR"), "/stubs-8.0.json")),
+            PhpVersion::Php81 => include_str!(concat!(env!("OUT_DIR"), "/stubs-8.1.json")),
+            PhpVersion::Php82 => include_str!(concat!(env!("OUT_DIR"), "/stubs-8.2.json")),
+            PhpVersion::Php83 => include_str!(concat!(env!("OUT_DIR"), "/stubs-8.3.json")),
+        };
+
+        serde_json::from_str(stubs).unwrap()
+    }
+}
+
+impl FromStr for PhpVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "8.0" => Ok(PhpVersion::Php80),
+            "8.1" => Ok(PhpVersion::Php81),
+            "8.2" => Ok(PhpVersion::Php82),
+            "8.3" => Ok(PhpVersion::Php83),
+            _ => Err(format!(
+                "unsupported PHP version {s}, expected one of 8.0, 8.1, 8.2 or 8.3"
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for PhpVersion {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for PhpVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PhpVersion::Php80 => write!(f, "8.0"),
+            PhpVersion::Php81 => write!(f, "8.1"),
+            PhpVersion::Php82 => write!(f, "8.2"),
+            PhpVersion::Php83 => write!(f, "8.3"),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+<?php
+
+function array_map(?callable $callback, array $array, array ...$arrays): array {}
+
+function array_filter(array $array, ?callable $callback = null, int $mode = 0): array {}
+
+function array_reduce(array $array, callable $callback, mixed $initial = null): mixed {}
+
+function array_walk(array|object &$array, callable $callback, mixed $arg = null): bool {}
+
+function array_keys(array $array, mixed $filter_value = null, bool $strict = false): array {}
+
+function array_values(array $array): array {}
+
+function array_merge(array ...$arrays): array {}
+
+function array_merge_recursive(array ...$arrays): array {}
+
+function array_replace(array $array, array ...$replacements): array {}
+
+function array_combine(array $keys, array $values): array {}
+
+function array_flip(array $array): array {}
+
+function array_slice(array $array, int $offset, ?int $length = null, bool $preserve_keys = false): array {}
+
+function array_splice(array &$array, int $offset, ?int $length = null, mixed $replacement = []): array {}
+
+function array_search(mixed $needle, array $haystack, bool $strict = false): int|string|false {}
+
+function in_array(mixed $needle, array $haystack, bool $strict = false): bool {}
+
+function array_key_exists($key, array $array): bool {}
+
+function key_exists($key, array $array): bool {}
+
+function array_key_first(array $array): int|string|null {}
+
+function array_key_last(array $array): int|string|null {}
+
+function array_push(array &$array, mixed ...$values): int {}
+
+function array_pop(array &$array): mixed {}
+
+function array_shift(array &$array): mixed {}
+
+function array_unshift(array &$array, mixed ...$values): int {}
+
+function array_reverse(array $array, bool $preserve_keys = false): array {}
+
+function array_unique(array $array, int $flags = SORT_STRING): array {}
+
+function array_column(array $array, int|string|null $column_key, int|string|null $index_key = null): array {}
+
+function array_chunk(array $array, int $length, bool $preserve_keys = false): array {}
+
+function array_fill(int $start_index, int $count, mixed $value): array {}
+
+function array_fill_keys(array $keys, mixed $value): array {}
+
+function array_pad(array $array, int $length, mixed $value): array {}
+
+function array_sum(array $array): int|float {}
+
+function array_product(array $array): int|float {}
+
+function array_count_values(array $array): array {}
+
+function array_diff(array $array, array ...$arrays): array {}
+
+function array_diff_key(array $array, array ...$arrays): array {}
+
+function array_diff_assoc(array $array, array ...$arrays): array {}
+
+function array_intersect(array $array, array ...$arrays): array {}
+
+function array_intersect_key(array $array, array ...$arrays): array {}
+
+function array_rand(array $array, int $num = 1): int|string|array {}
+
+function range($start, $end, int|float $step = 1): array {}
+
+function compact($var_name, ...$var_names): array {}
+
+function extract(array &$array, int $flags = EXTR_OVERWRITE, string $prefix = ""): int {}
+
+function sort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function rsort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function usort(array &$array, callable $callback): bool {}
+
+function uasort(array &$array, callable $callback): bool {}
+
+function uksort(array &$array, callable $callback): bool {}
+
+function ksort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function krsort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function asort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function arsort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function natsort(array &$array): bool {}
+
+function shuffle(array &$array): bool {}
+
+function current(array|object $array): mixed {}
+
+function pos(array|object $array): mixed {}
+
+function key(array|object $array): int|string|null {}
+
+function next(array|object &$array): mixed {}
+
+function prev(array|object &$array): mixed {}
+
+function reset(array|object &$array): mixed {}
+
+function end(array|object &$array): mixed {}
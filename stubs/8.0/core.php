@@ -0,0 +1,399 @@
+<?php
+
+interface Traversable
+{
+}
+
+interface Iterator extends Traversable
+{
+    public function current(): mixed;
+
+    public function next(): void;
+
+    public function key(): mixed;
+
+    public function valid(): bool;
+
+    public function rewind(): void;
+}
+
+interface IteratorAggregate extends Traversable
+{
+    public function getIterator(): Iterator;
+}
+
+interface ArrayAccess
+{
+    public function offsetExists(mixed $offset): bool;
+
+    public function offsetGet(mixed $offset): mixed;
+
+    public function offsetSet(mixed $offset, mixed $value): void;
+
+    public function offsetUnset(mixed $offset): void;
+}
+
+interface Countable
+{
+    public function count(): int;
+}
+
+interface Stringable
+{
+    public function __toString(): string;
+}
+
+interface Serializable
+{
+    public function serialize(): ?string;
+
+    public function unserialize(string $data): void;
+}
+
+interface Throwable extends Stringable
+{
+    public function getMessage(): string;
+
+    public function getCode(): int;
+
+    public function getFile(): string;
+
+    public function getLine(): int;
+
+    public function getTrace(): array;
+
+    public function getTraceAsString(): string;
+
+    public function getPrevious(): ?Throwable;
+}
+
+class stdClass
+{
+}
+
+class Exception implements Throwable
+{
+    final public function getMessage(): string {}
+
+    final public function getCode(): int {}
+
+    final public function getFile(): string {}
+
+    final public function getLine(): int {}
+
+    final public function getTrace(): array {}
+
+    final public function getTraceAsString(): string {}
+
+    final public function getPrevious(): ?Throwable {}
+
+    public function __toString(): string {}
+}
+
+class ErrorException extends Exception
+{
+    final public function getSeverity(): int {}
+}
+
+class Error implements Throwable
+{
+    final public function getMessage(): string {}
+
+    final public function getCode(): int {}
+
+    final public function getFile(): string {}
+
+    final public function getLine(): int {}
+
+    final public function getTrace(): array {}
+
+    final public function getTraceAsString(): string {}
+
+    final public function getPrevious(): ?Throwable {}
+
+    public function __toString(): string {}
+}
+
+class CompileError extends Error
+{
+}
+
+class ParseError extends CompileError
+{
+}
+
+class TypeError extends Error
+{
+}
+
+class ArgumentCountError extends TypeError
+{
+}
+
+class ValueError extends Error
+{
+}
+
+class ArithmeticError extends Error
+{
+}
+
+class DivisionByZeroError extends ArithmeticError
+{
+}
+
+class UnhandledMatchError extends Error
+{
+}
+
+final class Closure
+{
+    public static function bind(Closure $closure, ?object $newThis, object|string|null $newScope = 'static'): ?Closure {}
+
+    public function bindTo(?object $newThis, object|string|null $newScope = 'static'): ?Closure {}
+
+    public function call(object $newThis, mixed ...$args): mixed {}
+
+    public static function fromCallable(callable $callback): Closure {}
+}
+
+final class Generator implements Iterator
+{
+    public function current(): mixed {}
+
+    public function next(): void {}
+
+    public function key(): mixed {}
+
+    public function valid(): bool {}
+
+    public function rewind(): void {}
+
+    public function send(mixed $value): mixed {}
+
+    public function throw(Throwable $exception): mixed {}
+
+    public function getReturn(): mixed {}
+}
+
+final class WeakReference
+{
+    public static function create(object $object): WeakReference {}
+
+    public function get(): ?object {}
+}
+
+final class WeakMap implements ArrayAccess, Countable, IteratorAggregate
+{
+    public function offsetExists(mixed $offset): bool {}
+
+    public function offsetGet(mixed $offset): mixed {}
+
+    public function offsetSet(mixed $offset, mixed $value): void {}
+
+    public function offsetUnset(mixed $offset): void {}
+
+    public function count(): int {}
+
+    public function getIterator(): Iterator {}
+}
+
+final class Attribute
+{
+}
+
+function strlen(string $string): int {}
+
+function count(Countable|array $value, int $mode = COUNT_NORMAL): int {}
+
+function sizeof(Countable|array $value, int $mode = COUNT_NORMAL): int {}
+
+function var_dump(mixed $value, mixed ...$values): void {}
+
+function var_export(mixed $value, bool $return = false): ?string {}
+
+function print_r(mixed $value, bool $return = false): string|bool {}
+
+function debug_zval_refcount(mixed $value): int {}
+
+function serialize(mixed $value): string {}
+
+function unserialize(string $data, array $options = []): mixed {}
+
+function is_int(mixed $value): bool {}
+
+function is_integer(mixed $value): bool {}
+
+function is_float(mixed $value): bool {}
+
+function is_string(mixed $value): bool {}
+
+function is_bool(mixed $value): bool {}
+
+function is_array(mixed $value): bool {}
+
+function is_object(mixed $value): bool {}
+
+function is_null(mixed $value): bool {}
+
+function is_numeric(mixed $value): bool {}
+
+function is_scalar(mixed $value): bool {}
+
+function is_iterable(mixed $value): bool {}
+
+function is_countable(mixed $value): bool {}
+
+function is_resource(mixed $value): bool {}
+
+function is_callable(mixed $value, bool $syntax_only = false, &$callable_name = null): bool {}
+
+function is_a(mixed $object_or_class, string $class, bool $allow_string = false): bool {}
+
+function is_subclass_of(mixed $object_or_class, string $class, bool $allow_string = true): bool {}
+
+function intval(mixed $value, int $base = 10): int {}
+
+function floatval(mixed $value): float {}
+
+function boolval(mixed $value): bool {}
+
+function strval(mixed $value): string {}
+
+function settype(mixed &$var, string $type): bool {}
+
+function gettype(mixed $value): string {}
+
+function get_debug_type(mixed $value): string {}
+
+function get_class(object $object): string {}
+
+function get_parent_class(object|string $object_or_class): string|false {}
+
+function get_object_vars(object $object): array {}
+
+function get_class_methods(object|string $object_or_class): array {}
+
+function method_exists(object|string $object_or_class, string $method): bool {}
+
+function property_exists(object|string $object_or_class, string $property): bool {}
+
+function class_exists(string $class, bool $autoload = true): bool {}
+
+function interface_exists(string $interface, bool $autoload = true): bool {}
+
+function trait_exists(string $trait, bool $autoload = true): bool {}
+
+function function_exists(string $function): bool {}
+
+function class_implements(object|string $object_or_class, bool $autoload = true): array|false {}
+
+function class_uses(object|string $object_or_class, bool $autoload = true): array|false {}
+
+function call_user_func(callable $callback, mixed ...$args): mixed {}
+
+function call_user_func_array(callable $callback, array $args): mixed {}
+
+function func_get_args(): array {}
+
+function func_num_args(): int {}
+
+function define(string $constant_name, mixed $value, bool $case_insensitive = false): bool {}
+
+function defined(string $constant_name): bool {}
+
+function constant(string $name): mixed {}
+
+function error_reporting(?int $error_level = null): int {}
+
+function error_log(string $message, int $message_type = 0, ?string $destination = null, ?string $additional_headers = null): bool {}
+
+function trigger_error(string $message, int $error_level = E_USER_NOTICE): bool {}
+
+function set_error_handler(?callable $callback, int $error_levels = E_ALL) {}
+
+function restore_error_handler(): bool {}
+
+function set_exception_handler(?callable $callback) {}
+
+function restore_exception_handler(): bool {}
+
+function register_shutdown_function(callable $callback, mixed ...$args): void {}
+
+function spl_autoload_register(?callable $callback = null, bool $throw = true, bool $prepend = false): bool {}
+
+function spl_object_id(object $object): int {}
+
+function spl_object_hash(object $object): string {}
+
+function iterator_to_array(Traversable $iterator, bool $preserve_keys = true): array {}
+
+function iterator_count(Traversable $iterator): int {}
+
+function iterator_apply(Traversable $iterator, callable $callback, ?array $args = null): int {}
+
+function debug_backtrace(int $options = DEBUG_BACKTRACE_PROVIDE_OBJECT, int $limit = 0): array {}
+
+function debug_print_backtrace(int $options = 0, int $limit = 0): void {}
+
+function sleep(int $seconds): int {}
+
+function usleep(int $microseconds): void {}
+
+function time(): int {}
+
+function microtime(bool $as_float = false): string|float {}
+
+function hrtime(bool $as_number = false): array|int|float|false {}
+
+function uniqid(string $prefix = "", bool $more_entropy = false): string {}
+
+function random_int(int $min, int $max): int {}
+
+function random_bytes(int $length): string {}
+
+function getenv(?string $name = null, bool $local_only = false): array|string|false {}
+
+function putenv(string $assignment): bool {}
+
+function php_sapi_name(): string|false {}
+
+function phpversion(?string $extension = null): string|false {}
+
+function php_uname(string $mode = "a"): string {}
+
+function ini_get(string $option): string|false {}
+
+function ini_set(string $option, string|int|float|bool|null $value): string|false {}
+
+function set_time_limit(int $seconds): bool {}
+
+function memory_get_usage(bool $real_usage = false): int {}
+
+function memory_get_peak_usage(bool $real_usage = false): int {}
+
+function gc_collect_cycles(): int {}
+
+function version_compare(string $version1, string $version2, ?string $operator = null): int|bool {}
+
+function extension_loaded(string $extension): bool {}
+
+function header(string $header, bool $replace = true, int $response_code = 0): void {}
+
+function headers_sent(&$filename = null, &$line = null): bool {}
+
+function http_response_code(int $response_code = 0): int|bool {}
+
+function setcookie(string $name, string $value = "", array|int $expires_or_options = 0, string $path = "", string $domain = "", bool $secure = false, bool $httponly = false): bool {}
+
+function ob_start($callback = null, int $chunk_size = 0, int $flags = PHP_OUTPUT_HANDLER_STDFLAGS): bool {}
+
+function ob_get_clean(): string|false {}
+
+function ob_get_contents(): string|false {}
+
+function ob_end_clean(): bool {}
+
+function ob_end_flush(): bool {}
+
+function flush(): void {}
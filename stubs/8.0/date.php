@@ -0,0 +1,104 @@
+<?php
+
+interface DateTimeInterface
+{
+    public function format(string $format): string;
+
+    public function getTimezone(): DateTimeZone|false;
+
+    public function getOffset(): int;
+
+    public function getTimestamp(): int;
+
+    public function diff(DateTimeInterface $targetObject, bool $absolute = false): DateInterval;
+}
+
+class DateTimeZone
+{
+    public function getName(): string {}
+
+    public function getOffset(DateTimeInterface $datetime): int {}
+}
+
+class DateInterval
+{
+    public static function createFromDateString(string $datetime): DateInterval|false {}
+
+    public function format(string $format): string {}
+}
+
+class DateTime implements DateTimeInterface
+{
+    public function format(string $format): string {}
+
+    public function getTimezone(): DateTimeZone|false {}
+
+    public function getOffset(): int {}
+
+    public function getTimestamp(): int {}
+
+    public function diff(DateTimeInterface $targetObject, bool $absolute = false): DateInterval {}
+
+    public function modify(string $modifier): DateTime|false {}
+
+    public function add(DateInterval $interval): DateTime {}
+
+    public function sub(DateInterval $interval): DateTime {}
+
+    public function setDate(int $year, int $month, int $day): DateTime {}
+
+    public function setTime(int $hour, int $minute, int $second = 0, int $microsecond = 0): DateTime {}
+
+    public function setTimestamp(int $timestamp): DateTime {}
+
+    public function setTimezone(DateTimeZone $timezone): DateTime {}
+
+    public static function createFromFormat(string $format, string $datetime, ?DateTimeZone $timezone = null): DateTime|false {}
+
+    public static function createFromImmutable(DateTimeImmutable $object): DateTime {}
+}
+
+class DateTimeImmutable implements DateTimeInterface
+{
+    public function format(string $format): string {}
+
+    public function getTimezone(): DateTimeZone|false {}
+
+    public function getOffset(): int {}
+
+    public function getTimestamp(): int {}
+
+    public function diff(DateTimeInterface $targetObject, bool $absolute = false): DateInterval {}
+
+    public function modify(string $modifier): DateTimeImmutable|false {}
+
+    public function add(DateInterval $interval): DateTimeImmutable {}
+
+    public function sub(DateInterval $interval): DateTimeImmutable {}
+
+    public function setDate(int $year, int $month, int $day): DateTimeImmutable {}
+
+    public function setTime(int $hour, int $minute, int $second = 0, int $microsecond = 0): DateTimeImmutable {}
+
+    public function setTimestamp(int $timestamp): DateTimeImmutable {}
+
+    public function setTimezone(DateTimeZone $timezone): DateTimeImmutable {}
+
+    public static function createFromFormat(string $format, string $datetime, ?DateTimeZone $timezone = null): DateTimeImmutable|false {}
+
+    public static function createFromMutable(DateTime $object): DateTimeImmutable {}
+}
+
+function date(string $format, ?int $timestamp = null): string {}
+
+function gmdate(string $format, ?int $timestamp = null): string {}
+
+function mktime(int $hour, ?int $minute = null, ?int $second = null, ?int $month = null, ?int $day = null, ?int $year = null): int|false {}
+
+function strtotime(string $datetime, ?int $baseTimestamp = null): int|false {}
+
+function checkdate(int $month, int $day, int $year): bool {}
+
+function date_default_timezone_set(string $timezoneId): bool {}
+
+function date_default_timezone_get(): string {}
@@ -0,0 +1,91 @@
+<?php
+
+function file_get_contents(string $filename, bool $use_include_path = false, $context = null, int $offset = 0, ?int $length = null): string|false {}
+
+function file_put_contents(string $filename, mixed $data, int $flags = 0, $context = null): int|false {}
+
+function file(string $filename, int $flags = 0, $context = null): array|false {}
+
+function file_exists(string $filename): bool {}
+
+function is_file(string $filename): bool {}
+
+function is_dir(string $filename): bool {}
+
+function is_link(string $filename): bool {}
+
+function is_readable(string $filename): bool {}
+
+function is_writable(string $filename): bool {}
+
+function is_writeable(string $filename): bool {}
+
+function is_executable(string $filename): bool {}
+
+function filesize(string $filename): int|false {}
+
+function filemtime(string $filename): int|false {}
+
+function touch(string $filename, ?int $mtime = null, ?int $atime = null): bool {}
+
+function mkdir(string $directory, int $permissions = 0777, bool $recursive = false, $context = null): bool {}
+
+function rmdir(string $directory, $context = null): bool {}
+
+function unlink(string $filename, $context = null): bool {}
+
+function rename(string $from, string $to, $context = null): bool {}
+
+function copy(string $from, string $to, $context = null): bool {}
+
+function chmod(string $filename, int $permissions): bool {}
+
+function tempnam(string $directory, string $prefix): string|false {}
+
+function sys_get_temp_dir(): string {}
+
+function scandir(string $directory, int $sorting_order = SCANDIR_SORT_ASCENDING, $context = null): array|false {}
+
+function glob(string $pattern, int $flags = 0): array|false {}
+
+function dirname(string $path, int $levels = 1): string {}
+
+function basename(string $path, string $suffix = ""): string {}
+
+function pathinfo(string $path, int $flags = PATHINFO_ALL): array|string {}
+
+function realpath(string $path): string|false {}
+
+function getcwd(): string|false {}
+
+function chdir(string $directory): bool {}
+
+function fopen(string $filename, string $mode, bool $use_include_path = false, $context = null) {}
+
+function fclose($stream): bool {}
+
+function fwrite($stream, string $data, ?int $length = null): int|false {}
+
+function fputs($stream, string $data, ?int $length = null): int|false {}
+
+function fread($stream, int $length): string|false {}
+
+function fgets($stream, ?int $length = null): string|false {}
+
+function fgetcsv($stream, ?int $length = null, string $separator = ",", string $enclosure = "\"", string $escape = "\\"): array|false {}
+
+function fputcsv($stream, array $fields, string $separator = ",", string $enclosure = "\"", string $escape = "\\", string $eol = "\n"): int|false {}
+
+function feof($stream): bool {}
+
+function fflush($stream): bool {}
+
+function ftell($stream): int|false {}
+
+function fseek($stream, int $offset, int $whence = SEEK_SET): int {}
+
+function rewind($stream): bool {}
+
+function flock($stream, int $operation, &$would_block = null): bool {}
+
+function stream_get_contents($stream, ?int $length = null, int $offset = -1): string|false {}
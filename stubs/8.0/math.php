@@ -0,0 +1,65 @@
+<?php
+
+function abs(int|float $num): int|float {}
+
+function ceil(int|float $num): float {}
+
+function floor(int|float $num): float {}
+
+function round(int|float $num, int $precision = 0, int $mode = PHP_ROUND_HALF_UP): float {}
+
+function sqrt(float $num): float {}
+
+function pow(mixed $num, mixed $exponent): int|float|object {}
+
+function intdiv(int $num1, int $num2): int {}
+
+function fmod(float $num1, float $num2): float {}
+
+function fdiv(float $num1, float $num2): float {}
+
+function max(mixed $value, mixed ...$values): mixed {}
+
+function min(mixed $value, mixed ...$values): mixed {}
+
+function pi(): float {}
+
+function exp(float $num): float {}
+
+function log(float $num, float $base = M_E): float {}
+
+function log10(float $num): float {}
+
+function sin(float $num): float {}
+
+function cos(float $num): float {}
+
+function tan(float $num): float {}
+
+function is_nan(float $num): bool {}
+
+function is_finite(float $num): bool {}
+
+function is_infinite(float $num): bool {}
+
+function rand(int $min = 0, int $max = 0): int {}
+
+function mt_rand(int $min = 0, int $max = 0): int {}
+
+function mt_srand(int $seed = 0, int $mode = MT_RAND_MT19937): void {}
+
+function mt_getrandmax(): int {}
+
+function base_convert(string $num, int $from_base, int $to_base): string {}
+
+function bindec(string $binary_string): int|float {}
+
+function decbin(int $num): string {}
+
+function hexdec(string $hex_string): int|float {}
+
+function dechex(int $num): string {}
+
+function octdec(string $octal_string): int|float {}
+
+function decoct(int $num): string {}
@@ -0,0 +1,25 @@
+<?php
+
+function mb_strlen(string $string, ?string $encoding = null): int {}
+
+function mb_substr(string $string, int $start, ?int $length = null, ?string $encoding = null): string {}
+
+function mb_strtolower(string $string, ?string $encoding = null): string {}
+
+function mb_strtoupper(string $string, ?string $encoding = null): string {}
+
+function mb_strpos(string $haystack, string $needle, int $offset = 0, ?string $encoding = null): int|false {}
+
+function mb_strrpos(string $haystack, string $needle, int $offset = 0, ?string $encoding = null): int|false {}
+
+function mb_str_split(string $string, int $length = 1, ?string $encoding = null): array {}
+
+function mb_strwidth(string $string, ?string $encoding = null): int {}
+
+function mb_convert_case(string $string, int $mode, ?string $encoding = null): string {}
+
+function mb_convert_encoding(array|string $string, string $to_encoding, array|string|null $from_encoding = null): array|string|false {}
+
+function mb_check_encoding(array|string|null $value = null, ?string $encoding = null): bool {}
+
+function mb_internal_encoding(?string $encoding = null): string|bool {}
@@ -0,0 +1,156 @@
+<?php
+
+class LogicException extends Exception
+{
+}
+
+class BadFunctionCallException extends LogicException
+{
+}
+
+class BadMethodCallException extends BadFunctionCallException
+{
+}
+
+class DomainException extends LogicException
+{
+}
+
+class InvalidArgumentException extends LogicException
+{
+}
+
+class LengthException extends LogicException
+{
+}
+
+class OutOfRangeException extends LogicException
+{
+}
+
+class RuntimeException extends Exception
+{
+}
+
+class OutOfBoundsException extends RuntimeException
+{
+}
+
+class OverflowException extends RuntimeException
+{
+}
+
+class RangeException extends RuntimeException
+{
+}
+
+class UnderflowException extends RuntimeException
+{
+}
+
+class UnexpectedValueException extends RuntimeException
+{
+}
+
+class ArrayIterator implements Iterator, ArrayAccess, Countable
+{
+    public function current(): mixed {}
+
+    public function next(): void {}
+
+    public function key(): mixed {}
+
+    public function valid(): bool {}
+
+    public function rewind(): void {}
+
+    public function offsetExists(mixed $key): bool {}
+
+    public function offsetGet(mixed $key): mixed {}
+
+    public function offsetSet(mixed $key, mixed $value): void {}
+
+    public function offsetUnset(mixed $key): void {}
+
+    public function count(): int {}
+
+    public function getArrayCopy(): array {}
+}
+
+class ArrayObject implements IteratorAggregate, ArrayAccess, Countable
+{
+    public function getIterator(): Iterator {}
+
+    public function offsetExists(mixed $key): bool {}
+
+    public function offsetGet(mixed $key): mixed {}
+
+    public function offsetSet(mixed $key, mixed $value): void {}
+
+    public function offsetUnset(mixed $key): void {}
+
+    public function count(): int {}
+
+    public function append(mixed $value): void {}
+
+    public function getArrayCopy(): array {}
+
+    public function exchangeArray(array|object $array): array {}
+}
+
+class SplObjectStorage implements Countable, Iterator, ArrayAccess
+{
+    public function attach(object $object, mixed $info = null): void {}
+
+    public function detach(object $object): void {}
+
+    public function contains(object $object): bool {}
+
+    public function current(): object {}
+
+    public function next(): void {}
+
+    public function key(): int {}
+
+    public function valid(): bool {}
+
+    public function rewind(): void {}
+
+    public function offsetExists($object): bool {}
+
+    public function offsetGet($object): mixed {}
+
+    public function offsetSet(mixed $object, mixed $info = null): void {}
+
+    public function offsetUnset($object): void {}
+
+    public function count(int $mode = COUNT_NORMAL): int {}
+}
+
+class SplStack implements Countable, IteratorAggregate
+{
+    public function push(mixed $value): void {}
+
+    public function pop(): mixed {}
+
+    public function top(): mixed {}
+
+    public function isEmpty(): bool {}
+
+    public function count(): int {}
+
+    public function getIterator(): Iterator {}
+}
+
+class SplQueue implements Countable, IteratorAggregate
+{
+    public function enqueue(mixed $value): void {}
+
+    public function dequeue(): mixed {}
+
+    public function isEmpty(): bool {}
+
+    public function count(): int {}
+
+    public function getIterator(): Iterator {}
+}
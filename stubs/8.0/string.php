@@ -0,0 +1,163 @@
+<?php
+
+function str_contains(string $haystack, string $needle): bool {}
+
+function str_starts_with(string $haystack, string $needle): bool {}
+
+function str_ends_with(string $haystack, string $needle): bool {}
+
+function strtolower(string $string): string {}
+
+function strtoupper(string $string): string {}
+
+function ucfirst(string $string): string {}
+
+function lcfirst(string $string): string {}
+
+function ucwords(string $string, string $separators = " \t\r\n\f\v"): string {}
+
+function trim(string $string, string $characters = " \n\r\t\v\x00"): string {}
+
+function ltrim(string $string, string $characters = " \n\r\t\v\x00"): string {}
+
+function rtrim(string $string, string $characters = " \n\r\t\v\x00"): string {}
+
+function chop(string $string, string $characters = " \n\r\t\v\x00"): string {}
+
+function str_replace(array|string $search, array|string $replace, string|array $subject, &$count = null): string|array {}
+
+function str_ireplace(array|string $search, array|string $replace, string|array $subject, &$count = null): string|array {}
+
+function str_repeat(string $string, int $times): string {}
+
+function str_pad(string $string, int $length, string $pad_string = " ", int $pad_type = STR_PAD_RIGHT): string {}
+
+function str_split(string $string, int $length = 1): array {}
+
+function str_word_count(string $string, int $format = 0, ?string $characters = null): array|int {}
+
+function strpos(string $haystack, string $needle, int $offset = 0): int|false {}
+
+function stripos(string $haystack, string $needle, int $offset = 0): int|false {}
+
+function strrpos(string $haystack, string $needle, int $offset = 0): int|false {}
+
+function strstr(string $haystack, string $needle, bool $before_needle = false): string|false {}
+
+function stristr(string $haystack, string $needle, bool $before_needle = false): string|false {}
+
+function strrchr(string $haystack, string $needle): string|false {}
+
+function substr(string $string, int $offset, ?int $length = null): string {}
+
+function substr_count(string $haystack, string $needle, int $offset = 0, ?int $length = null): int {}
+
+function substr_replace(array|string $string, array|string $replace, array|int $offset, array|int|null $length = null): string|array {}
+
+function strrev(string $string): string {}
+
+function strcmp(string $string1, string $string2): int {}
+
+function strcasecmp(string $string1, string $string2): int {}
+
+function strncmp(string $string1, string $string2, int $length): int {}
+
+function strncasecmp(string $string1, string $string2, int $length): int {}
+
+function strnatcmp(string $string1, string $string2): int {}
+
+function strnatcasecmp(string $string1, string $string2): int {}
+
+function strtr(string $string, string|array $from, ?string $to = null): string {}
+
+function sprintf(string $format, mixed ...$values): string {}
+
+function vsprintf(string $format, array $values): string {}
+
+function printf(string $format, mixed ...$values): int {}
+
+function vprintf(string $format, array $values): int {}
+
+function sscanf(string $string, string $format, mixed &...$vars): array|int|null {}
+
+function number_format(float $num, int $decimals = 0, ?string $decimal_separator = ".", ?string $thousands_separator = ","): string {}
+
+function implode(array|string $separator, ?array $array = null): string {}
+
+function join(array|string $separator, ?array $array = null): string {}
+
+function explode(string $separator, string $string, int $limit = PHP_INT_MAX): array {}
+
+function nl2br(string $string, bool $use_xhtml = true): string {}
+
+function htmlspecialchars(string $string, int $flags = ENT_QUOTES | ENT_SUBSTITUTE | ENT_HTML401, ?string $encoding = null, bool $double_encode = true): string {}
+
+function htmlspecialchars_decode(string $string, int $flags = ENT_QUOTES | ENT_SUBSTITUTE | ENT_HTML401): string {}
+
+function htmlentities(string $string, int $flags = ENT_QUOTES | ENT_SUBSTITUTE | ENT_HTML401, ?string $encoding = null, bool $double_encode = true): string {}
+
+function html_entity_decode(string $string, int $flags = ENT_QUOTES | ENT_SUBSTITUTE | ENT_HTML401, ?string $encoding = null): string {}
+
+function strip_tags(string $string, array|string|null $allowed_tags = null): string {}
+
+function addslashes(string $string): string {}
+
+function stripslashes(string $string): string {}
+
+function quotemeta(string $string): string {}
+
+function wordwrap(string $string, int $width = 75, string $break = "\n", bool $cut_long_words = false): string {}
+
+function chunk_split(string $string, int $length = 76, string $separator = "\r\n"): string {}
+
+function similar_text(string $string1, string $string2, &$percent = null): int {}
+
+function levenshtein(string $string1, string $string2, int $insertion_cost = 1, int $replacement_cost = 1, int $deletion_cost = 1): int {}
+
+function soundex(string $string): string {}
+
+function metaphone(string $string, int $max_phonemes = 0): string {}
+
+function ord(string $character): int {}
+
+function chr(int $codepoint): string {}
+
+function md5(string $string, bool $binary = false): string {}
+
+function sha1(string $string, bool $binary = false): string {}
+
+function crc32(string $string): int {}
+
+function hash(string $algo, string $data, bool $binary = false, array $options = []): string {}
+
+function hash_hmac(string $algo, string $data, string $key, bool $binary = false): string {}
+
+function hash_equals(string $known_string, string $user_string): bool {}
+
+function password_hash(string $password, string|int|null $algo, array $options = []): string {}
+
+function password_verify(string $password, string $hash): bool {}
+
+function base64_encode(string $string): string {}
+
+function base64_decode(string $string, bool $strict = false): string|false {}
+
+function bin2hex(string $string): string {}
+
+function hex2bin(string $string): string|false {}
+
+function urlencode(string $string): string {}
+
+function urldecode(string $string): string {}
+
+function rawurlencode(string $string): string {}
+
+function rawurldecode(string $string): string {}
+
+function http_build_query(array|object $data, string $numeric_prefix = "", ?string $arg_separator = null, int $encoding_type = PHP_QUERY_RFC1738): string {}
+
+function parse_str(string $string, &$result): void {}
+
+function parse_url(string $url, int $component = -1): int|string|array|null|false {}
+
+function filter_var(mixed $value, int $filter = FILTER_DEFAULT, array|int $options = 0): mixed {}
@@ -0,0 +1,52 @@
+<?php
+
+interface UnitEnum
+{
+    public static function cases(): array;
+}
+
+interface BackedEnum extends UnitEnum
+{
+    public static function from(int|string $value): static;
+
+    public static function tryFrom(int|string $value): ?static;
+}
+
+final class Fiber
+{
+    public function start(mixed ...$args): mixed {}
+
+    public function resume(mixed $value = null): mixed {}
+
+    public function throw(Throwable $exception): mixed {}
+
+    public function getReturn(): mixed {}
+
+    public function isStarted(): bool {}
+
+    public function isSuspended(): bool {}
+
+    public function isRunning(): bool {}
+
+    public function isTerminated(): bool {}
+
+    public static function suspend(mixed $value = null): mixed {}
+
+    public static function getCurrent(): ?Fiber {}
+}
+
+class FiberError extends Error
+{
+}
+
+final class ReturnTypeWillChange
+{
+}
+
+function enum_exists(string $enum, bool $autoload = true): bool {}
+
+function array_is_list(array $array): bool {}
+
+function fsync($stream): bool {}
+
+function fdatasync($stream): bool {}
@@ -0,0 +1,13 @@
+<?php
+
+final class AllowDynamicProperties
+{
+}
+
+final class SensitiveParameter
+{
+}
+
+function ini_parse_quantity(string $shorthand): int {}
+
+function memory_reset_peak_usage(): void {}
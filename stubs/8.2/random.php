@@ -0,0 +1,18 @@
+<?php
+
+namespace Random;
+
+final class Randomizer
+{
+    public function nextInt(): int {}
+
+    public function getInt(int $min, int $max): int {}
+
+    public function getBytes(int $length): string {}
+
+    public function shuffleArray(array $array): array {}
+
+    public function shuffleBytes(string $bytes): string {}
+
+    public function pickArrayKeys(array $array, int $num): array {}
+}
@@ -0,0 +1,13 @@
+<?php
+
+final class Override
+{
+}
+
+function json_validate(string $json, int $depth = 512, int $flags = 0): bool {}
+
+function mb_str_pad(string $string, int $length, string $pad_string = " ", int $pad_type = STR_PAD_RIGHT, ?string $encoding = null): string {}
+
+function str_increment(string $string): string {}
+
+function str_decrement(string $string): string {}